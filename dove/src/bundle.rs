@@ -0,0 +1,493 @@
+use core::mem;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Error, Result};
+use serde::{Serialize, Deserialize};
+use diem_crypto::HashValue;
+
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::CompiledModule;
+use move_core_types::language_storage::ModuleId;
+
+use crate::context::Context;
+use crate::{DOVE_VERSION, DOVE_HASH, DIEM_VERSION, DIEM_HASH};
+
+/// Signature of the versioned bundle.
+const BUNDLE_MAGIC: &[u8; 4] = b"DPAC";
+/// Current version of the bundle format.
+pub const BUNDLE_FORMAT_VERSION: u16 = 1;
+/// Project directories with the sources included in the source digest.
+const SOURCE_DIRS: [&str; 2] = ["sources", "scripts"];
+
+/// Legacy bundle: bytecode of the modules in the dependency order.
+/// This is the layout the node accepts.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ModulePackage {
+    modules: Vec<Vec<u8>>,
+}
+
+impl ModulePackage {
+    pub fn put(&mut self, module: Vec<u8>) {
+        self.modules.push(module);
+    }
+
+    /// Bytecode of the modules.
+    pub fn modules(&self) -> &[Vec<u8>] {
+        &self.modules
+    }
+
+    /// Orders the modules so that each module follows its dependencies.
+    /// Fails if the modules have a dependency cycle.
+    pub fn sort(&mut self) -> Result<(), Error> {
+        let mut modules = Vec::with_capacity(self.modules.len());
+        mem::swap(&mut self.modules, &mut modules);
+
+        let mut modules = modules
+            .into_iter()
+            .map(|bytecode| {
+                CompiledModule::deserialize(&bytecode)
+                    .map(|unit| (unit.self_id(), (bytecode, unit)))
+                    .map_err(|_| anyhow!("Failed to deserialize move module."))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        let mut ids_list: Vec<_> = modules.keys().cloned().collect();
+        ids_list.sort();
+
+        let mut path = Vec::new();
+        for id in ids_list {
+            self.write_sub_tree(&id, &mut modules, &mut path)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_sub_tree(
+        &mut self,
+        id: &ModuleId,
+        modules: &mut HashMap<ModuleId, (Vec<u8>, CompiledModule)>,
+        path: &mut Vec<ModuleId>,
+    ) -> Result<(), Error> {
+        if let Some(pos) = path.iter().position(|module| module == id) {
+            let cycle = path[pos..]
+                .iter()
+                .chain(Some(id))
+                .map(module_id_to_string)
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            bail!("Dependency cycle detected: {}", cycle);
+        }
+
+        if let Some((bytecode, unit)) = modules.remove(id) {
+            path.push(id.clone());
+            let deps = Self::take_deps(id, &unit);
+            for dep in deps {
+                self.write_sub_tree(&dep, modules, path)?;
+            }
+            path.pop();
+            println!("Packing '{}'...", id.name());
+            self.modules.push(bytecode);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn take_deps(id: &ModuleId, unit: &CompiledModule) -> Vec<ModuleId> {
+        unit.module_handles()
+            .iter()
+            .map(|hdl| unit.module_id_for_handle(hdl))
+            .filter(|dep_id| dep_id != id)
+            .collect()
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, Error> {
+        bcs::to_bytes(&self).map_err(|err| err.into())
+    }
+}
+
+/// Package metadata of the versioned bundle.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BundleMetadata {
+    /// Package name from Move.toml.
+    pub name: String,
+    /// Package version from Move.toml.
+    pub version: String,
+    /// SHA3-256 of Move.toml and the package sources.
+    pub source_digest: String,
+    /// Modules in the dependency order.
+    pub modules: Vec<ModuleHash>,
+    /// Versions of the tools the bundle was built with.
+    pub build_info: BuildInfo,
+}
+
+/// Module of the bundle.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ModuleHash {
+    /// Module id: 0x1::Module
+    pub id: String,
+    /// SHA3-256 of the module bytecode.
+    pub hash: String,
+}
+
+/// Versions of the tools the bundle was built with.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BuildInfo {
+    /// Dove version and commit.
+    pub dove: String,
+    /// Move (Diem) version and commit.
+    pub move_version: String,
+}
+
+impl BuildInfo {
+    /// Versions of the current dove build.
+    pub fn current() -> BuildInfo {
+        BuildInfo {
+            dove: join_version(DOVE_VERSION, DOVE_HASH),
+            move_version: join_version(DIEM_VERSION, DIEM_HASH),
+        }
+    }
+}
+
+/// Versioned bundle.
+///     DPAC | format version (u16, LE) | BCS(metadata, modules)
+#[derive(Serialize, Deserialize)]
+struct BundleV1 {
+    metadata: BundleMetadata,
+    modules: Vec<Vec<u8>>,
+}
+
+/// Bundle of the versioned or the legacy format.
+#[derive(Debug)]
+pub struct Bundle {
+    /// Package metadata. None for the legacy bundle.
+    pub metadata: Option<BundleMetadata>,
+    /// Modules in the dependency order.
+    pub package: ModulePackage,
+}
+
+impl Bundle {
+    /// Creates the versioned bundle of the project modules.
+    /// The modules must be sorted.
+    pub fn new(ctx: &Context, package: ModulePackage) -> Result<Bundle> {
+        let modules = package
+            .modules()
+            .iter()
+            .map(|bytecode| {
+                let module = CompiledModule::deserialize(bytecode)
+                    .map_err(|_| anyhow!("Failed to deserialize move module."))?;
+                Ok(ModuleHash {
+                    id: module_id_to_string(&module.self_id()),
+                    hash: HashValue::sha3_256_of(bytecode).to_hex(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let (major, minor, patch) = ctx.manifest.package.version;
+        Ok(Bundle {
+            metadata: Some(BundleMetadata {
+                name: ctx.manifest.package.name.to_string(),
+                version: format!("{}.{}.{}", major, minor, patch),
+                source_digest: source_digest(&ctx.project_root_dir)?,
+                modules,
+                build_info: BuildInfo::current(),
+            }),
+            package,
+        })
+    }
+
+    /// Reads the bundle of any format.
+    pub fn read(path: &Path) -> Result<Bundle> {
+        let bytes = fs::read(path)?;
+        Bundle::decode(&bytes).map_err(|err| anyhow!("Failed to read bundle {:?}: {}", path, err))
+    }
+
+    /// Decodes the bundle of any format.
+    pub fn decode(bytes: &[u8]) -> Result<Bundle> {
+        if !Bundle::is_versioned(bytes) {
+            let package: ModulePackage = bcs::from_bytes(bytes)
+                .map_err(|err| anyhow!("Invalid bundle format: {}", err))?;
+            return Ok(Bundle {
+                metadata: None,
+                package,
+            });
+        }
+
+        let header = BUNDLE_MAGIC.len();
+        let version = u16::from_le_bytes([bytes[header], bytes[header + 1]]);
+        ensure!(
+            (1..=BUNDLE_FORMAT_VERSION).contains(&version),
+            "Bundle format version {} is not supported. The latest supported version is {}. Please update dove",
+            version,
+            BUNDLE_FORMAT_VERSION
+        );
+        let bundle: BundleV1 = bcs::from_bytes(&bytes[header + 2..])
+            .map_err(|err| anyhow!("Invalid bundle format: {}", err))?;
+        Ok(Bundle {
+            metadata: Some(bundle.metadata),
+            package: ModulePackage {
+                modules: bundle.modules,
+            },
+        })
+    }
+
+    /// Encodes the bundle. A bundle without metadata is encoded in the legacy format.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        match &self.metadata {
+            Some(metadata) => {
+                let mut bytes = BUNDLE_MAGIC.to_vec();
+                bytes.extend_from_slice(&BUNDLE_FORMAT_VERSION.to_le_bytes());
+                bytes.extend(bcs::to_bytes(&BundleV1 {
+                    metadata: metadata.clone(),
+                    modules: self.package.modules.clone(),
+                })?);
+                Ok(bytes)
+            }
+            None => self.package.encode(),
+        }
+    }
+
+    /// Encodes the bundle in the legacy format accepted by the node.
+    pub fn encode_legacy(&self) -> Result<Vec<u8>> {
+        self.package.encode()
+    }
+
+    /// Returns true if the bytes start with the versioned bundle signature.
+    pub fn is_versioned(bytes: &[u8]) -> bool {
+        bytes.len() > BUNDLE_MAGIC.len() + 2 && bytes.starts_with(BUNDLE_MAGIC)
+    }
+}
+
+/// Modules selected for the bundle.
+#[derive(Debug, Default)]
+pub struct Selection {
+    /// Selected modules and their dependencies.
+    pub package: ModulePackage,
+    /// Dependencies added to the selection: (dependency, dependent module).
+    pub added: Vec<(ModuleId, ModuleId)>,
+    /// Excluded modules the selected ones depend on: (dependency, dependent module).
+    pub excluded_deps: Vec<(ModuleId, ModuleId)>,
+}
+
+/// Selects the modules matching the include patterns (all modules if there are no patterns)
+/// and not matching the exclude patterns, then adds their transitive dependencies.
+/// Excluded modules are never added, the excluded dependencies are reported instead.
+/// Patterns are case-insensitive module names with an optional .mv extension and wildcards: Demo, Demo.mv, Demo*, *_v?
+pub fn select_modules(
+    modules: Vec<Vec<u8>>,
+    include: &[String],
+    exclude: &[String],
+) -> Result<Selection> {
+    let units = modules
+        .into_iter()
+        .map(|bytecode| {
+            CompiledModule::deserialize(&bytecode)
+                .map(|unit| (unit.self_id(), (bytecode, unit)))
+                .map_err(|_| anyhow!("Failed to deserialize move module."))
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
+
+    let matches_any = |patterns: &[String], id: &ModuleId| {
+        patterns
+            .iter()
+            .any(|pattern| matches_pattern(pattern, id.name().as_str()))
+    };
+    for pattern in include {
+        ensure!(
+            units
+                .keys()
+                .any(|id| matches_pattern(pattern, id.name().as_str())),
+            "No modules match '{}'",
+            pattern
+        );
+    }
+
+    let mut queue = units
+        .keys()
+        .filter(|id| include.is_empty() || matches_any(include, id))
+        .filter(|id| !matches_any(exclude, id))
+        .cloned()
+        .collect::<Vec<_>>();
+    queue.sort();
+    let mut selected = queue.iter().cloned().collect::<BTreeSet<_>>();
+
+    let mut selection = Selection::default();
+    while let Some(id) = queue.pop() {
+        let (_, unit) = &units[&id];
+        for dep in ModulePackage::take_deps(&id, unit) {
+            if !units.contains_key(&dep) || selected.contains(&dep) {
+                continue;
+            }
+            if matches_any(exclude, &dep) {
+                if !selection
+                    .excluded_deps
+                    .iter()
+                    .any(|(excluded, _)| excluded == &dep)
+                {
+                    selection.excluded_deps.push((dep, id.clone()));
+                }
+                continue;
+            }
+            selected.insert(dep.clone());
+            selection.added.push((dep.clone(), id.clone()));
+            queue.push(dep);
+        }
+    }
+
+    for id in selected {
+        let (bytecode, _) = &units[&id];
+        selection.package.put(bytecode.clone());
+    }
+    selection.package.sort()?;
+    Ok(selection)
+}
+
+/// Case-insensitive match of the module name with the pattern.
+/// The pattern can end with .mv and contain the wildcards: * - any characters, ? - one character.
+pub(crate) fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    let pattern = pattern.strip_suffix(".mv").unwrap_or(&pattern);
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.to_lowercase().chars().collect::<Vec<_>>();
+
+    // matched[j] - the pattern prefix matches the first j characters of the name
+    let mut matched = vec![false; name.len() + 1];
+    matched[0] = true;
+    for ch in pattern {
+        let mut next = vec![false; name.len() + 1];
+        for j in 0..=name.len() {
+            next[j] = match ch {
+                '*' => matched[j] || (j > 0 && next[j - 1]),
+                '?' => j > 0 && matched[j - 1],
+                ch => j > 0 && matched[j - 1] && name[j - 1] == ch,
+            };
+        }
+        matched = next;
+    }
+    matched[name.len()]
+}
+
+/// Module id as a string: 0x1::Module
+pub fn module_id_to_string(id: &ModuleId) -> String {
+    format!("{}::{}", id.address().to_hex_literal(), id.name())
+}
+
+/// SHA3-256 of Move.toml and the files from the source directories ordered by path.
+pub fn source_digest(project_dir: &Path) -> Result<String> {
+    let mut files = vec![project_dir.join("Move.toml")];
+    for dir in SOURCE_DIRS {
+        let dir = project_dir.join(dir);
+        if dir.exists() {
+            files.extend(source_files(&dir)?);
+        }
+    }
+    files.sort();
+
+    let mut content = Vec::new();
+    for file in files {
+        let name = file.strip_prefix(project_dir).unwrap_or(&file);
+        content.extend(name.to_string_lossy().replace('\\', "/").as_bytes());
+        content.extend(fs::read(&file)?);
+    }
+    Ok(HashValue::sha3_256_of(&content).to_hex())
+}
+
+fn source_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(source_files(&path)?);
+        } else if path.extension().map(|ext| ext == "move").unwrap_or(false) {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+fn join_version(version: &str, hash: &str) -> String {
+    [version, hash]
+        .iter()
+        .filter(|str| !str.is_empty())
+        .map(|str| str.to_string())
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matches_pattern, Bundle, BundleMetadata, BuildInfo, ModuleHash, ModulePackage};
+
+    fn metadata() -> BundleMetadata {
+        BundleMetadata {
+            name: "Demo".to_string(),
+            version: "0.1.0".to_string(),
+            source_digest: "00".to_string(),
+            modules: vec![ModuleHash {
+                id: "0x1::Demo".to_string(),
+                hash: "01".to_string(),
+            }],
+            build_info: BuildInfo::current(),
+        }
+    }
+
+    fn package() -> ModulePackage {
+        let mut package = ModulePackage::default();
+        package.put(vec![1, 2, 3]);
+        package
+    }
+
+    #[test]
+    fn test_versioned_bundle() {
+        let bundle = Bundle {
+            metadata: Some(metadata()),
+            package: package(),
+        };
+        let bytes = bundle.encode().unwrap();
+        assert!(Bundle::is_versioned(&bytes));
+
+        let decoded = Bundle::decode(&bytes).unwrap();
+        assert_eq!(decoded.metadata, Some(metadata()));
+        assert_eq!(decoded.package.modules(), &[vec![1, 2, 3]]);
+        assert_eq!(
+            decoded.encode_legacy().unwrap(),
+            package().encode().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_legacy_bundle() {
+        let bytes = package().encode().unwrap();
+        assert!(!Bundle::is_versioned(&bytes));
+
+        let decoded = Bundle::decode(&bytes).unwrap();
+        assert_eq!(decoded.metadata, None);
+        assert_eq!(decoded.package.modules(), &[vec![1, 2, 3]]);
+        assert_eq!(decoded.encode().unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let mut bytes = Bundle {
+            metadata: Some(metadata()),
+            package: package(),
+        }
+        .encode()
+        .unwrap();
+        bytes[4] = 0xFF;
+        assert!(Bundle::decode(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("Demo", "Demo"));
+        assert!(matches_pattern("demo.mv", "Demo"));
+        assert!(matches_pattern("Demo*", "Demo1v"));
+        assert!(matches_pattern("*1v", "Demo1v"));
+        assert!(matches_pattern("Demo?v", "Demo2v"));
+        assert!(matches_pattern("*", "Demo"));
+        assert!(!matches_pattern("Demo", "Demo1v"));
+        assert!(!matches_pattern("Demo?", "Demo"));
+        assert!(!matches_pattern("*2v", "Demo1v"));
+    }
+}
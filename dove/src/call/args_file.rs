@@ -0,0 +1,184 @@
+use std::fs;
+use std::path::Path;
+use anyhow::Error;
+use serde_json::Value;
+use crate::call::parser::CallArgs;
+
+/// Loads call arguments from a JSON or TOML file.
+///
+/// An array is treated as positional arguments:
+///     [[10, 10], true, "ALIAS_ADDRESSES", "100"]
+/// An object binds arguments to the function parameters by name:
+///     { "to": "0x1", "amount": 100 }
+/// TOML has no top-level array, so the arguments are passed by the `args` key,
+/// an array or a table:
+///     args = [[10, 10], true, "ALIAS_ADDRESSES", "100"]
+///     args = { to = "0x1", amount = 100 }
+///
+/// The values are converted to the argument syntax of the call declaration
+/// and then coerced using the function parameter types.
+/// Integers that do not fit into u64 should be passed as strings.
+pub(crate) fn load_args(path: &Path) -> Result<CallArgs, Error> {
+    let value = match read_file(path)? {
+        Value::Object(mut values) if is_toml(path) => values.remove("args").ok_or_else(|| {
+            anyhow!(
+                "Failed to load arguments from {:?}. 'args' key was expected.",
+                path
+            )
+        })?,
+        value => value,
+    };
+    value_to_args(&value)
+        .map_err(|err| anyhow!("Failed to load arguments from {:?}. {}", path, err))
+}
+
+/// Converts an array to positional arguments and an object to named arguments.
+pub(crate) fn value_to_args(value: &Value) -> Result<CallArgs, Error> {
+    Ok(match value {
+        Value::Array(values) => CallArgs::Positional(
+            values
+                .iter()
+                .map(value_to_arg)
+                .collect::<Result<_, Error>>()?,
+        ),
+        Value::Object(values) => CallArgs::Named(
+            values
+                .iter()
+                .map(|(name, value)| value_to_arg(value).map(|value| (name.to_owned(), value)))
+                .collect::<Result<_, Error>>()?,
+        ),
+        _ => bail!("An array or an object was expected."),
+    })
+}
+
+/// Loads type arguments from a JSON or TOML file.
+///
+/// JSON: ["0x1::Coins::ETH", "u8"]
+/// TOML: type_args = ["0x1::Coins::ETH", "u8"]
+pub(crate) fn load_type_args(path: &Path) -> Result<Vec<String>, Error> {
+    let values = match read_file(path)? {
+        Value::Array(values) => values,
+        Value::Object(mut values) => match values.remove("type_args") {
+            Some(Value::Array(values)) => values,
+            _ => bail!(
+                "Failed to load type arguments from {:?}. 'type_args' array was expected.",
+                path
+            ),
+        },
+        _ => bail!(
+            "Failed to load type arguments from {:?}. An array was expected.",
+            path
+        ),
+    };
+
+    values
+        .into_iter()
+        .map(|value| match value {
+            Value::String(tp) => Ok(tp),
+            _ => Err(anyhow!("Type argument must be a string. Actual: {}", value)),
+        })
+        .collect()
+}
+
+/// Reads a JSON or TOML file. The format is chosen by the file extension.
+pub(crate) fn read_file(path: &Path) -> Result<Value, Error> {
+    let content =
+        fs::read_to_string(path).map_err(|err| anyhow!("Failed to read {:?}: {}", path, err))?;
+    if is_toml(path) {
+        toml::from_str(&content).map_err(|err| anyhow!("Failed to parse {:?}: {}", path, err))
+    } else {
+        serde_json::from_str(&content)
+            .map_err(|err| anyhow!("Failed to parse {:?}: {}", path, err))
+    }
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase() == "toml")
+        .unwrap_or(false)
+}
+
+/// Converts a value to the argument syntax of the call declaration.
+///
+/// [[1, 2], "0102", true] => "[[1,2],0102,true]"
+fn value_to_arg(value: &Value) -> Result<String, Error> {
+    Ok(match value {
+        Value::Bool(val) => val.to_string(),
+        Value::Number(val) => val.to_string(),
+        Value::String(val) => val.to_owned(),
+        Value::Array(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(value_to_arg)
+                .collect::<Result<Vec<_>, Error>>()?
+                .join(",")
+        ),
+        Value::Null | Value::Object(_) => bail!("Unsupported argument value: {}", value),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use serde_json::json;
+    use crate::call::parser::CallArgs;
+    use super::{load_args, value_to_arg};
+
+    #[test]
+    fn test_value_to_arg() {
+        assert_eq!(value_to_arg(&json!(true)).unwrap(), "true");
+        assert_eq!(value_to_arg(&json!(100)).unwrap(), "100");
+        assert_eq!(
+            value_to_arg(&json!("340282366920938463463374607431768211455")).unwrap(),
+            "340282366920938463463374607431768211455"
+        );
+        assert_eq!(
+            value_to_arg(&json!([[1, 2], "0102", []])).unwrap(),
+            "[[1,2],0102,[]]"
+        );
+        assert_eq!(
+            value_to_arg(&json!([
+                "0x1",
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+            ]))
+            .unwrap(),
+            "[0x1,5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY]"
+        );
+        assert!(value_to_arg(&json!(null)).is_err());
+        assert!(value_to_arg(&json!({"a": 1})).is_err());
+    }
+
+    fn load_toml(name: &str, content: &str) -> anyhow::Result<CallArgs> {
+        let path =
+            std::env::temp_dir().join(format!("dove_{}_{}.toml", name, std::process::id()));
+        fs::write(&path, content).unwrap();
+        let args = load_args(&path);
+        fs::remove_file(&path).unwrap();
+        args
+    }
+
+    #[test]
+    fn test_load_toml_positional_args() {
+        match load_toml("positional", "args = [[1, 2], true, \"100\"]").unwrap() {
+            CallArgs::Positional(args) => assert_eq!(args, vec!["[1,2]", "true", "100"]),
+            CallArgs::Named(_) => panic!("Positional arguments were expected"),
+        }
+    }
+
+    #[test]
+    fn test_load_toml_named_args() {
+        // A parameter may be called `args`.
+        match load_toml("named", "[args]\nargs = [1, 2]\namount = 100").unwrap() {
+            CallArgs::Named(args) => assert_eq!(
+                args,
+                vec![
+                    ("amount".to_string(), "100".to_string()),
+                    ("args".to_string(), "[1,2]".to_string())
+                ]
+            ),
+            CallArgs::Positional(_) => panic!("Named arguments were expected"),
+        }
+        assert!(load_toml("no_args", "amount = 100").is_err());
+    }
+}
@@ -2,9 +2,11 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use anyhow::Error;
 use move_binary_format::CompiledModule;
-use move_binary_format::file_format::{CompiledScript, empty_module};
+use move_binary_format::file_format::{CompiledScript, empty_module, FunctionDefinitionIndex};
+use move_bytecode_source_map::source_map::SourceMap;
 use move_package::compilation::package_layout::CompiledPackageLayout;
 use lang::bytecode::accessor::{Bytecode, BytecodeAccess, BytecodeRef, BytecodeType};
+use lang::bytecode::info::BytecodeInfo;
 use crate::context::Context;
 
 /// Dove bytecode resolver.
@@ -19,6 +21,55 @@ impl DoveBytecode {
             path: ctx.path_for_build(None, CompiledPackageLayout::Root),
         }
     }
+
+    /// Returns the bytecode directories of the packages:
+    ///     ./build/<package>/bytecode_scripts
+    ///     ./build/<package>/bytecode_modules
+    ///     ./build/<package>/bytecode_modules/dependencies/<dependency>
+    fn bytecode_dirs(
+        &self,
+        package: Option<&str>,
+        tp: Option<BytecodeType>,
+    ) -> Result<Vec<(PathBuf, BytecodeType)>, Error> {
+        let is_selected = |name: &str| package.map(|package| package == name).unwrap_or(true);
+        let mut dirs = Vec::new();
+        for root in fs::read_dir(&self.path)?
+            .filter_map(|path| path.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+        {
+            let root_name = root
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            if !matches!(tp, Some(BytecodeType::Module)) && is_selected(&root_name) {
+                let scripts = root.join(CompiledPackageLayout::CompiledScripts.path());
+                if scripts.exists() {
+                    dirs.push((scripts, BytecodeType::Script));
+                }
+            }
+
+            if !matches!(tp, Some(BytecodeType::Script)) {
+                let modules = root.join(CompiledPackageLayout::CompiledModules.path());
+                if modules.exists() && is_selected(&root_name) {
+                    dirs.push((modules.clone(), BytecodeType::Module));
+                }
+
+                let dependencies = modules.join(CompiledPackageLayout::Dependencies.path());
+                if let Ok(entries) = fs::read_dir(&dependencies) {
+                    for dependency in entries
+                        .filter_map(|path| path.ok())
+                        .filter(|entry| entry.path().is_dir())
+                        .filter(|entry| is_selected(&entry.file_name().to_string_lossy()))
+                    {
+                        dirs.push((dependency.path(), BytecodeType::Module));
+                    }
+                }
+            }
+        }
+        Ok(dirs)
+    }
 }
 
 impl BytecodeAccess for DoveBytecode {
@@ -28,56 +79,9 @@ impl BytecodeAccess for DoveBytecode {
         name: Option<&'a str>,
         tp: Option<BytecodeType>,
     ) -> Result<Vec<BytecodeRef>, Error> {
-        Ok(fs::read_dir(&self.path)?
-            .filter_map(|path| path.ok())
-            .filter(|path| path.path().is_dir())
-            .filter_map(|path| {
-                if let Some(package) = package {
-                    if package == path.file_name().to_string_lossy() {
-                        Some(path.path())
-                    } else {
-                        None
-                    }
-                } else {
-                    Some(path.path())
-                }
-            })
-            .filter_map(|path| {
-                let mut path_vec = Vec::with_capacity(2);
-                fn push_if_exists(
-                    base: &Path,
-                    buffer: &mut Vec<(PathBuf, BytecodeType)>,
-                    tp: BytecodeType,
-                ) {
-                    let tp_path = match tp {
-                        BytecodeType::Script => CompiledPackageLayout::CompiledScripts.path(),
-                        BytecodeType::Module => CompiledPackageLayout::CompiledModules.path(),
-                    };
-
-                    let path = base.join(tp_path);
-                    if path.exists() {
-                        buffer.push((path, tp));
-                    }
-                }
-                match tp {
-                    None => {
-                        push_if_exists(&path, &mut path_vec, BytecodeType::Script);
-                        push_if_exists(&path, &mut path_vec, BytecodeType::Module);
-                    }
-                    Some(BytecodeType::Script) => {
-                        push_if_exists(&path, &mut path_vec, BytecodeType::Script);
-                    }
-                    Some(BytecodeType::Module) => {
-                        push_if_exists(&path, &mut path_vec, BytecodeType::Module);
-                    }
-                }
-                if path_vec.is_empty() {
-                    None
-                } else {
-                    Some(path_vec)
-                }
-            })
-            .flatten()
+        Ok(self
+            .bytecode_dirs(package, tp)?
+            .into_iter()
             .filter_map(|(path, tp)| {
                 let dir = fs::read_dir(path)
                     .ok()?
@@ -148,3 +152,87 @@ impl BytecodeAccess for DoveBytecode {
         }))
     }
 }
+
+/// Returns the name of the package the bytecode belongs to.
+///     ./build/<package>/bytecode_[modules|scripts]/<name>.mv
+///     ./build/<package>/bytecode_modules/dependencies/<dependency>/<name>.mv
+pub fn package_name(rf: &BytecodeRef) -> Option<String> {
+    dependency_name(rf.0.as_ref()).or_else(|| build_package_name(rf))
+}
+
+/// Returns the name of the package build directory the bytecode is stored in.
+///     ./build/<package>/...
+pub fn build_package_name(rf: &BytecodeRef) -> Option<String> {
+    package_dir(rf.0.as_ref())
+        .and_then(|p| p.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+/// Returns the name of the dependency package if the bytecode is a dependency module.
+///     ./build/<package>/bytecode_modules/dependencies/<dependency>/<name>.mv
+fn dependency_name(path: &Path) -> Option<String> {
+    let dir = path.parent()?;
+    let dependencies = dir.parent()?;
+    let modules = dependencies.parent()?;
+    if dependencies.ends_with(CompiledPackageLayout::Dependencies.path())
+        && modules.ends_with(CompiledPackageLayout::CompiledModules.path())
+    {
+        dir.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    } else {
+        None
+    }
+}
+
+/// Returns the package build directory of the bytecode.
+fn package_dir(path: &Path) -> Option<&Path> {
+    let depth = if dependency_name(path).is_some() {
+        4
+    } else {
+        2
+    };
+    path.ancestors().nth(depth)
+}
+
+/// Returns the path to the source map of the bytecode.
+///     ./build/<package>/source_maps/<name>.mvsm
+///     ./build/<package>/source_maps/dependencies/<dependency>/<name>.mvsm
+fn source_map_path(path: &Path) -> Result<PathBuf, Error> {
+    let name = path
+        .file_stem()
+        .ok_or_else(|| anyhow!("Failed to get file name:{:?}", path))?;
+    let mut source_maps = package_dir(path)
+        .ok_or_else(|| anyhow!("Failed to get package dir:{:?}", path))?
+        .join(CompiledPackageLayout::SourceMaps.path());
+    if let Some(dependency) = dependency_name(path) {
+        source_maps = source_maps
+            .join(CompiledPackageLayout::Dependencies.path())
+            .join(dependency);
+    }
+    Ok(source_maps.join(name).with_extension("mvsm"))
+}
+
+/// Returns the parameter names of the function.
+/// Names are not stored in the bytecode, so they are taken from the source map
+/// which the compiler generates from the Move sources.
+pub fn parameter_names(info: &BytecodeInfo, func: &str) -> Result<Vec<String>, Error> {
+    let index = info
+        .function_index(func)
+        .ok_or_else(|| anyhow!("Function {} not found in {}", func, info.name()))?;
+
+    let source_map_path = source_map_path(info.bytecode_ref().0.as_ref())?;
+    let source_map: SourceMap = bcs::from_bytes(&fs::read(&source_map_path).map_err(|err| {
+        anyhow!(
+            "Failed to read source map {:?} to resolve parameter names: {}",
+            source_map_path,
+            err
+        )
+    })?)?;
+
+    Ok(source_map
+        .get_function_source_map(FunctionDefinitionIndex(index))?
+        .parameters
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect())
+}
@@ -2,9 +2,10 @@ use anyhow::Error;
 use structopt::StructOpt;
 use std::fmt::Debug;
 use std::convert::TryFrom;
-use std::mem;
-use move_package::source_package::parsed_manifest::AddressDeclarations;
-use crate::call::parser::{parse_call, Call, parse_tp_param};
+use std::path::PathBuf;
+use lang::address::AddressResolver;
+use crate::call::args_file::{load_args, load_type_args};
+use crate::call::parser::{parse_call, Call, parse_tp_param, CallArgs};
 
 #[derive(StructOpt, Debug)]
 #[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
@@ -13,11 +14,13 @@ pub struct CallDeclarationCmd {
 Examples:
       'create_balance<0x01::Dfinance::USD>([10,10], true, ALIAS_ADDRESSES, 100, 0x1)'
       'script_name()'
+      'transfer(to: ALIAS_ADDRESSES, amount: 100)'
       'Module::function()'
       'ALIAS_ADDRESSES::Module::function()'
       '0x1::Module::function' --args [10,10] true ALIAS_ADDRESSES 100 0x1 --type 0x01::Dfinance::USD
+      '0x1::Module::function' --args-file args.json --type-args-file types.json
       "#)]
-    call: String,
+    call: Option<String>,
     #[structopt(
         help = r#"Script type parameters, e.g. 0x1::Dfinance::USD"#,
         name = "Script type parameters.",
@@ -32,6 +35,28 @@ Examples:
         short = "a"
     )]
     params: Option<Vec<String>>,
+    #[structopt(
+        help = r#"Path to a JSON or TOML file with script arguments.
+An array is passed positionally, an object binds arguments by parameter name:
+      [[10, 10], true, "ALIAS_ADDRESSES", "100"]
+      {"to": "0x1", "amount": 100}
+TOML passes the array or the table by the args key:
+      args = [[10, 10], true, "ALIAS_ADDRESSES", "100"]
+      args = { to = "0x1", amount = 100 }"#,
+        name = "Script arguments file.",
+        long = "args-file",
+        parse(from_os_str),
+        conflicts_with = "Script arguments."
+    )]
+    args_file: Option<PathBuf>,
+    #[structopt(
+        help = r#"Path to a JSON or TOML file with script type parameters, e.g. ["0x1::Dfinance::USD"]"#,
+        name = "Script type parameters file.",
+        long = "type-args-file",
+        parse(from_os_str),
+        conflicts_with = "Script type parameters."
+    )]
+    type_args_file: Option<PathBuf>,
     #[structopt(
         help = r#"Move package name"#,
         name = "Move package name.",
@@ -46,29 +71,42 @@ impl CallDeclarationCmd {
     #[must_use]
     pub fn take(&mut self) -> Self {
         Self {
-            call: mem::take(&mut self.call),
+            call: self.call.take(),
             type_parameters: self.type_parameters.take(),
             params: self.params.take(),
+            args_file: self.args_file.take(),
+            type_args_file: self.type_args_file.take(),
             package: self.package.take(),
         }
     }
 }
 
-impl TryFrom<(&AddressDeclarations, CallDeclarationCmd)> for CallDeclaration {
+impl TryFrom<(&AddressResolver, CallDeclarationCmd)> for CallDeclaration {
     type Error = Error;
 
     fn try_from(
-        (addr_map, cmd): (&AddressDeclarations, CallDeclarationCmd),
+        (resolver, cmd): (&AddressResolver, CallDeclarationCmd),
     ) -> Result<Self, Self::Error> {
-        let mut call = parse_call(addr_map, &cmd.call)?;
+        let call = cmd
+            .call
+            .as_deref()
+            .ok_or_else(|| anyhow!("Call declaration is not specified"))?;
+        let mut call = parse_call(resolver, call)?;
         if let Some(args) = cmd.params {
-            call.set_args(args);
+            call.set_args(CallArgs::Positional(args));
+        }
+        if let Some(path) = cmd.args_file {
+            call.set_args(load_args(&path)?);
         }
 
-        if let Some(tp) = cmd.type_parameters {
+        let type_parameters = match cmd.type_args_file {
+            Some(path) => Some(load_type_args(&path)?),
+            None => cmd.type_parameters,
+        };
+        if let Some(tp) = type_parameters {
             call.set_tp_params(
                 tp.iter()
-                    .map(|tp| parse_tp_param(addr_map, tp))
+                    .map(|tp| parse_tp_param(resolver, tp))
                     .collect::<Result<_, _>>()?,
             );
         }
@@ -1,20 +1,20 @@
 use std::str::FromStr;
 use std::fmt::Debug;
 use anyhow::Error;
-use move_symbol_pool::Symbol;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{CORE_CODE_ADDRESS, TypeTag};
-use move_package::source_package::parsed_manifest::AddressDeclarations;
+use lang::address::AddressResolver;
 use lang::bytecode::accessor::BytecodeType;
 use lang::bytecode::{find, SearchParams};
-use lang::bytecode::info::{BytecodeInfo, Type};
+use lang::bytecode::info::{BytecodeInfo, Script, Type};
 use crate::context::Context;
 use crate::call::model::{Signer, ScriptArg, Transaction, Signers, EnrichedTransaction, Call};
-use crate::call::parser::parse_vec;
-use crate::call::bytecode::DoveBytecode;
+use crate::call::parser::{parse_vec, split_vec, CallArgs};
+use crate::call::bytecode::{DoveBytecode, parameter_names};
+use crate::call::suggestion::{explain, Target, Unresolved};
 
-fn diem_root_address() -> AccountAddress {
+pub(crate) fn diem_root_address() -> AccountAddress {
     AccountAddress::from_hex_literal("0xA550C18")
         .expect("Parsing valid hex literal should always succeed")
 }
@@ -47,10 +47,10 @@ impl Config {
 
 pub(crate) fn make_script_call(
     ctx: &Context,
-    addr_map: &AddressDeclarations,
+    resolver: &AddressResolver,
     name: Identifier,
     type_tag: Vec<TypeTag>,
-    args: Vec<String>,
+    args: CallArgs,
     package_name: Option<String>,
     cfg: Config,
 ) -> Result<EnrichedTransaction, Error> {
@@ -65,7 +65,8 @@ pub(crate) fn make_script_call(
     )?
     .filter_map(|f| f.ok());
     let (signers, args, info) =
-        select_function(functions, &name, &args, &type_tag, &cfg, addr_map)?;
+        select_function(functions, &name, &args, &type_tag, &cfg, resolver)
+            .map_err(|err| explain(err, ctx, &Target::Script(name.as_str())))?;
 
     Ok(if cfg.tx_context {
         let (_, mut tx) = match signers {
@@ -112,12 +113,12 @@ pub(crate) fn make_script_call(
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn make_function_call(
     ctx: &Context,
-    addr_map: &AddressDeclarations,
+    resolver: &AddressResolver,
     address: Option<AccountAddress>,
     module: Identifier,
     func: Identifier,
     type_tag: Vec<TypeTag>,
-    args: Vec<String>,
+    args: CallArgs,
     package_name: Option<String>,
     cfg: Config,
 ) -> Result<EnrichedTransaction, Error> {
@@ -139,8 +140,15 @@ pub(crate) fn make_function_call(
         }
     })
     .filter(|info| info.name() == module.as_str());
-    let (signers, args, info) =
-        select_function(modules, &func, &args, &type_tag, &cfg, addr_map)?;
+    let (signers, args, info) = select_function(modules, &func, &args, &type_tag, &cfg, resolver)
+        .map_err(|err| {
+            let target = Target::Function {
+                address,
+                module: module.as_str(),
+                func: func.as_str(),
+            };
+            explain(err, ctx, &target)
+        })?;
 
     let addr = info.address().unwrap_or(CORE_CODE_ADDRESS);
     let tx_name = format!("{}_{}", module, func);
@@ -178,54 +186,148 @@ pub(crate) fn make_function_call(
 fn select_function<I>(
     info_iter: I,
     name: &Identifier,
-    args: &[String],
+    args: &CallArgs,
     type_tag: &[TypeTag],
     cfg: &Config,
-    addr_map: &AddressDeclarations,
+    resolver: &AddressResolver,
 ) -> Result<(Signers, Vec<ScriptArg>, BytecodeInfo), Error>
 where
     I: Iterator<Item = BytecodeInfo>,
 {
-    let mut functions = info_iter
-        .filter_map(|info| info.find_script_function(name.as_str()).map(|f| (info, f)))
-        .filter(|(_, f)| type_tag.len() == f.type_params_count())
-        .map(|(i, script)| {
+    let mut unresolved = Unresolved::default();
+    let mut functions = vec![];
+    for info in info_iter {
+        let script = match info.find_script_function(name.as_str()) {
+            Some(script) => script,
+            None => continue,
+        };
+
+        if type_tag.len() != script.type_params_count() {
+            let reason = format!(
+                "Expected {} type arguments, but {} were passed.",
+                script.type_params_count(),
+                type_tag.len()
+            );
+            unresolved.reject(&info, &script, reason);
+            continue;
+        }
+
+        let signature = positional_args(&info, &script, args).and_then(|args| {
             prepare_function_signature(
                 &script.parameters,
-                args,
+                &args,
                 !cfg.deny_signers_definition,
-                addr_map,
+                resolver,
             )
-            .map(|(signers, args)| (i, script, signers, args))
-        })
-        .collect::<Vec<Result<_, _>>>();
-    let count = functions.iter().filter(|r| r.is_ok()).count();
-    if count == 0 {
-        if functions.is_empty() {
-            bail!("Couldn't find a function with given signature.");
-        } else {
-            functions.remove(0)?;
-            unreachable!();
+        });
+        match signature {
+            Ok((signers, args)) => functions.push((info, signers, args)),
+            Err(err) => unresolved.reject(&info, &script, err.to_string()),
         }
-    } else if count > 1 {
-        bail!(
+    }
+
+    match functions.len() {
+        0 => Err(unresolved.into()),
+        1 => {
+            let (info, signers, args) = functions.remove(0);
+            Ok((signers, args, info))
+        }
+        _ => bail!(
             "More than one functions with the given signature was found.\
                    Please pass the package name to specify the package or use unique signatures."
+        ),
+    }
+}
+
+/// Returns the call arguments in the order of the function parameters.
+fn positional_args(
+    info: &BytecodeInfo,
+    script: &Script,
+    args: &CallArgs,
+) -> Result<Vec<String>, Error> {
+    match args {
+        CallArgs::Positional(args) => Ok(args.to_owned()),
+        CallArgs::Named(args) => {
+            let names = parameter_names(info, &script.name)?;
+            order_named_args(&names, &script.parameters, args)
+        }
+    }
+}
+
+/// Orders named arguments by the function parameters.
+/// Signers can be omitted, but if one of them is passed, all of them must be passed.
+fn order_named_args(
+    names: &[String],
+    code_args: &[Type],
+    args: &[(String, String)],
+) -> Result<Vec<String>, Error> {
+    ensure!(
+        names.len() == code_args.len(),
+        "Parameter names do not match the function signature. Please rebuild the project."
+    );
+
+    for (index, (name, _)) in args.iter().enumerate() {
+        ensure!(
+            names.contains(name),
+            "Unknown parameter '{}'. The function parameters: {}",
+            name,
+            names.join(", ")
+        );
+        ensure!(
+            !args[..index].iter().any(|(prev, _)| prev == name),
+            "Parameter '{}' is passed more than once",
+            name
         );
-    } else {
-        let (bytecode_info, _, signers, args) = functions
-            .into_iter()
-            .find_map(|res| res.ok())
-            .ok_or_else(|| anyhow!("Couldn't find a function with given signature."))?;
-        Ok((signers, args, bytecode_info))
     }
+
+    let signers_count = code_args
+        .iter()
+        .take_while(|tp| **tp == Type::Signer)
+        .count();
+    let find_arg = |name: &String| {
+        args.iter()
+            .find(|(arg_name, _)| arg_name == name)
+            .map(|(_, value)| value.to_owned())
+    };
+    let passed_signers = names[..signers_count].iter().filter_map(find_arg).count();
+    ensure!(
+        passed_signers == 0 || passed_signers == signers_count,
+        "Either all signers or none of them must be passed: {}",
+        names[..signers_count].join(", ")
+    );
+
+    let missing = names
+        .iter()
+        .skip(if passed_signers == 0 {
+            signers_count
+        } else {
+            0
+        })
+        .filter(|name| find_arg(name).is_none())
+        .map(|name| name.as_str())
+        .collect::<Vec<_>>();
+    ensure!(
+        missing.is_empty(),
+        "Missing arguments: {}",
+        missing.join(", ")
+    );
+
+    Ok(names
+        .iter()
+        .skip(if passed_signers == 0 {
+            signers_count
+        } else {
+            0
+        })
+        .filter_map(find_arg)
+        .collect())
 }
 
 fn prepare_function_signature(
     code_args: &[Type],
     call_args: &[String],
     use_explicit_signers: bool,
-    addr_map: &AddressDeclarations,
+    resolver: &AddressResolver,
 ) -> Result<(Signers, Vec<ScriptArg>), Error> {
     let signers_count = code_args
         .iter()
@@ -245,7 +347,7 @@ fn prepare_function_signature(
     let params = code_args[signers_count..]
         .iter()
         .zip(&call_args[args_index..])
-        .map(|(tp, val)| prepare_arg(tp, val, addr_map))
+        .map(|(tp, val)| prepare_arg(tp, val, resolver))
         .collect::<Result<Vec<_>, Error>>()?;
 
     if use_explicit_signers {
@@ -264,11 +366,7 @@ fn prepare_function_signature(
                                     "Use explicit signer instead of placeholder"
                                 ));
                             }
-                            Signer::Name(name) => {
-                                addr_map.get(&name).and_then(|addr| *addr).ok_or_else(|| {
-                                    anyhow!("Failed to find address with name:{}", arg)
-                                })?
-                            }
+                            Signer::Name(_) => resolver.resolve_account(arg)?,
                         })
                     })
                 }
@@ -302,7 +400,7 @@ fn prepare_function_signature(
 fn prepare_arg(
     arg_type: &Type,
     arg_value: &str,
-    addr_map: &AddressDeclarations,
+    resolver: &AddressResolver,
 ) -> Result<ScriptArg, Error> {
     macro_rules! parse_primitive {
         ($script_arg:expr) => {
@@ -319,7 +417,7 @@ fn prepare_arg(
         Type::U8 => parse_primitive!(ScriptArg::U8),
         Type::U64 => parse_primitive!(ScriptArg::U64),
         Type::U128 => parse_primitive!(ScriptArg::U128),
-        Type::Address => ScriptArg::Address(parse_address(arg_value, addr_map)?),
+        Type::Address => ScriptArg::Address(resolver.resolve_account(arg_value)?),
         Type::Vector(tp) => match tp.as_ref() {
             Type::Bool => ScriptArg::VectorBool(
                 parse_vec(arg_value, "bool")
@@ -328,24 +426,32 @@ fn prepare_arg(
             Type::U8 => ScriptArg::VectorU8(if arg_value.contains('[') {
                 parse_vec(arg_value, "u8").map_err(|err| parse_err(arg_type, arg_value, err))?
             } else {
-                hex::decode(arg_value).map_err(|err| parse_err(arg_type, arg_value, err))?
+                hex::decode(arg_value.trim_start_matches("0x"))
+                    .map_err(|err| parse_err(arg_type, arg_value, err))?
             }),
             Type::U64 => ScriptArg::VectorU64(
                 parse_vec(arg_value, "u64").map_err(|err| parse_err(arg_type, arg_value, err))?,
             ),
             Type::U128 => ScriptArg::VectorU128(
-                parse_vec(arg_value, "u64").map_err(|err| parse_err(arg_type, arg_value, err))?,
+                parse_vec(arg_value, "u128")
+                    .map_err(|err| parse_err(arg_type, arg_value, err))?,
             ),
             Type::Address => {
-                let addresses = parse_vec::<String>(arg_value, "vector<address>")
+                let addresses = split_vec(arg_value)
                     .map_err(|err| parse_err(arg_type, arg_value, err))?
                     .into_iter()
-                    .map(|addr| parse_address(&addr, addr_map))
+                    .map(|addr| resolver.resolve_account(&addr))
                     .collect::<Result<Vec<_>, Error>>()?;
                 ScriptArg::VectorAddress(addresses)
             }
+            Type::Vector(_) => ScriptArg::Vector(
+                split_vec(arg_value)
+                    .map_err(|err| parse_err(arg_type, arg_value, err))?
+                    .iter()
+                    .map(|value| prepare_arg(tp, value, resolver))
+                    .collect::<Result<Vec<_>, Error>>()?,
+            ),
             Type::Signer
-            | Type::Vector(_)
             | Type::Struct(_)
             | Type::Reference(_)
             | Type::MutableReference(_)
@@ -361,19 +467,6 @@ fn prepare_arg(
     })
 }
 
-fn parse_address(
-    arg_value: &str,
-    addr_map: &AddressDeclarations,
-) -> Result<AccountAddress, Error> {
-    match pontem::parse_address(arg_value) {
-        Ok(addr) => Ok(addr),
-        Err(_) => addr_map
-            .get(&Symbol::from(arg_value))
-            .and_then(|addr| *addr)
-            .ok_or_else(|| anyhow!("Failed to find address with name:{}", arg_value)),
-    }
-}
-
 fn parse_err<D: Debug>(tp: &Type, value: &str, err: D) -> Error {
     anyhow!(
         "Parameter has type {:?}. Failed to parse {}. Error:'{:?}'",
@@ -388,8 +481,8 @@ mod call_tests {
     use move_core_types::language_storage::CORE_CODE_ADDRESS;
     use move_core_types::account_address::AccountAddress;
     use lang::bytecode::info::Type;
-    use crate::call::model::ScriptArg;
-    use crate::call::fn_call::prepare_function_signature;
+    use crate::call::model::{ScriptArg, Signers};
+    use crate::call::fn_call::{prepare_function_signature, order_named_args};
 
     fn s(v: &str) -> String {
         v.to_string()
@@ -482,4 +575,130 @@ mod call_tests {
             ]
         );
     }
+
+    #[test]
+    fn test_address_spellings() {
+        let alice = addr("0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+        let (signers, args) = prepare_function_signature(
+            &[
+                Type::Signer,
+                Type::Address,
+                Type::Vector(Box::new(Type::Address)),
+            ],
+            &[
+                s("//Alice"),
+                s("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+                s("[alice, 0x1]"),
+            ],
+            true,
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(signers, Signers::Explicit(vec![alice]));
+        assert_eq!(
+            args,
+            vec![
+                ScriptArg::Address(alice),
+                ScriptArg::VectorAddress(vec![alice, addr("0x1")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_vector_args() {
+        let vec_of = |tp: Type| Type::Vector(Box::new(tp));
+
+        let (_, args) = prepare_function_signature(
+            &[
+                vec_of(vec_of(Type::U8)),
+                vec_of(vec_of(Type::Address)),
+                vec_of(vec_of(vec_of(Type::U64))),
+            ],
+            &[
+                s("[0102, [3, 4], [], 0x05]"),
+                s("[[0x1], [0x1, 0x2]]"),
+                s("[[[1], []], []]"),
+            ],
+            true,
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                ScriptArg::Vector(vec![
+                    ScriptArg::VectorU8(vec![1, 2]),
+                    ScriptArg::VectorU8(vec![3, 4]),
+                    ScriptArg::VectorU8(vec![]),
+                    ScriptArg::VectorU8(vec![5]),
+                ]),
+                ScriptArg::Vector(vec![
+                    ScriptArg::VectorAddress(vec![addr("0x1")]),
+                    ScriptArg::VectorAddress(vec![addr("0x1"), addr("0x2")]),
+                ]),
+                ScriptArg::Vector(vec![
+                    ScriptArg::Vector(vec![
+                        ScriptArg::VectorU64(vec![1]),
+                        ScriptArg::VectorU64(vec![]),
+                    ]),
+                    ScriptArg::Vector(vec![]),
+                ]),
+            ]
+        );
+
+        assert!(prepare_function_signature(
+            &[vec_of(vec_of(Type::U8))],
+            &[s("[[1, 2]")],
+            true,
+            &Default::default(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_order_named_args() {
+        let names = [s("account"), s("to"), s("amount")];
+        let code_args = [Type::Signer, Type::Address, Type::U64];
+        let named = |args: &[(&str, &str)]| {
+            args.iter()
+                .map(|(name, value)| (s(name), s(value)))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            order_named_args(
+                &names,
+                &code_args,
+                &named(&[("amount", "100"), ("to", "0x1")])
+            )
+            .unwrap(),
+            vec![s("0x1"), s("100")]
+        );
+        assert_eq!(
+            order_named_args(
+                &names,
+                &code_args,
+                &named(&[("amount", "100"), ("account", "0x2"), ("to", "0x1")])
+            )
+            .unwrap(),
+            vec![s("0x2"), s("0x1"), s("100")]
+        );
+
+        // unknown parameter
+        assert!(order_named_args(
+            &names,
+            &code_args,
+            &named(&[("to", "0x1"), ("amount", "1"), ("value", "1")])
+        )
+        .is_err());
+        // missing parameter
+        assert!(order_named_args(&names, &code_args, &named(&[("to", "0x1")])).is_err());
+        // duplicate parameter
+        assert!(order_named_args(
+            &names,
+            &code_args,
+            &named(&[("to", "0x1"), ("to", "0x2"), ("amount", "1")])
+        )
+        .is_err());
+    }
 }
@@ -5,6 +5,8 @@ use crate::call::fn_call::{Config, make_function_call, make_script_call};
 use crate::call::model::EnrichedTransaction;
 use crate::call::parser::Call;
 
+/// Arguments file.
+pub mod args_file;
 /// Bytecode.
 pub mod bytecode;
 /// Command helper.
@@ -15,6 +17,12 @@ pub mod fn_call;
 pub mod model;
 /// Call parser.
 pub mod parser;
+/// Call sequence file.
+pub mod sequence;
+/// Transaction simulation.
+pub mod simulate;
+/// Suggestions for unresolved calls.
+mod suggestion;
 
 /// Make transaction with given call declaration.
 pub fn make_transaction(
@@ -22,8 +30,18 @@ pub fn make_transaction(
     cmd: CallDeclarationCmd,
     cfg: Config,
 ) -> Result<EnrichedTransaction, Error> {
-    let address_decl = ctx.address_declarations();
-    let declaration = CallDeclaration::try_from((&address_decl, cmd))?;
+    let resolver = ctx.address_resolver();
+    let declaration = CallDeclaration::try_from((&resolver, cmd))?;
+    make_declared_transaction(ctx, declaration, cfg)
+}
+
+/// Make transaction with given parsed call declaration.
+pub fn make_declared_transaction(
+    ctx: &Context,
+    declaration: CallDeclaration,
+    cfg: Config,
+) -> Result<EnrichedTransaction, Error> {
+    let resolver = ctx.address_resolver();
     match declaration.call {
         Call::Function {
             address,
@@ -33,7 +51,7 @@ pub fn make_transaction(
             args,
         } => make_function_call(
             ctx,
-            &address_decl,
+            &resolver,
             address,
             module,
             func,
@@ -48,7 +66,7 @@ pub fn make_transaction(
             args,
         } => make_script_call(
             ctx,
-            &address_decl,
+            &resolver,
             name,
             type_tag,
             args,
@@ -131,6 +131,8 @@ pub enum ScriptArg {
     VectorBool(Vec<bool>),
     /// vector<address>
     VectorAddress(Vec<AccountAddress>),
+    /// vector<vector<...>>
+    Vector(Vec<ScriptArg>),
 }
 
 impl From<ScriptArg> for MoveValue {
@@ -154,6 +156,9 @@ impl From<ScriptArg> for MoveValue {
             ScriptArg::VectorAddress(val) => {
                 MoveValue::Vector(val.into_iter().map(MoveValue::Address).collect())
             }
+            ScriptArg::Vector(val) => {
+                MoveValue::Vector(val.into_iter().map(MoveValue::from).collect())
+            }
         }
     }
 }
@@ -172,7 +177,8 @@ impl TryInto<TransactionArgument> for ScriptArg {
             ScriptArg::VectorU64(_)
             | ScriptArg::VectorU128(_)
             | ScriptArg::VectorBool(_)
-            | ScriptArg::VectorAddress(_) => bail!("Unssuported transaction args."),
+            | ScriptArg::VectorAddress(_)
+            | ScriptArg::Vector(_) => bail!("Unssuported transaction args."),
         })
     }
 }
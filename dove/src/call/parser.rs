@@ -9,8 +9,7 @@ use std::str::FromStr;
 use move_command_line_common::files::FileHash;
 use move_compiler::Flags;
 use move_compiler::shared::CompilationEnv;
-use move_package::source_package::parsed_manifest::AddressDeclarations;
-use move_symbol_pool::Symbol;
+use lang::address::AddressResolver;
 
 const ERROR_MESSAGE: &str = "Invalid call format: expected function identifier.\n\n\
          Use pattern:\n\
@@ -32,7 +31,7 @@ pub enum Call {
         /// Function type parameter.
         type_tag: Vec<TypeTag>,
         /// Function args.
-        args: Vec<String>,
+        args: CallArgs,
     },
     /// Script call declaration.
     Script {
@@ -41,12 +40,47 @@ pub enum Call {
         /// Function type parameter.
         type_tag: Vec<TypeTag>,
         /// Function args.
-        args: Vec<String>,
+        args: CallArgs,
     },
 }
 
+/// Call arguments.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CallArgs {
+    /// Arguments in the order of the function parameters.
+    Positional(Vec<String>),
+    /// Arguments bound to the function parameters by name.
+    Named(Vec<(String, String)>),
+}
+
+impl Default for CallArgs {
+    fn default() -> Self {
+        CallArgs::Positional(vec![])
+    }
+}
+
+impl CallArgs {
+    #[cfg(test)]
+    pub fn positional(self) -> Vec<String> {
+        if let CallArgs::Positional(args) = self {
+            args
+        } else {
+            panic!("Positional arguments are expected")
+        }
+    }
+
+    #[cfg(test)]
+    pub fn named(self) -> Vec<(String, String)> {
+        if let CallArgs::Named(args) = self {
+            args
+        } else {
+            panic!("Named arguments are expected")
+        }
+    }
+}
+
 impl Call {
-    pub(crate) fn set_args(&mut self, new_args: Vec<String>) {
+    pub(crate) fn set_args(&mut self, new_args: CallArgs) {
         match self {
             Call::Function { args, .. } => {
                 *args = new_args;
@@ -68,6 +102,14 @@ impl Call {
         }
     }
 
+    #[cfg(test)]
+    pub fn args(self) -> CallArgs {
+        match self {
+            Call::Function { args, .. } => args,
+            Call::Script { args, .. } => args,
+        }
+    }
+
     #[cfg(test)]
     pub fn script(self) -> (Identifier, Vec<TypeTag>, Vec<String>) {
         if let Call::Script {
@@ -76,7 +118,7 @@ impl Call {
             args,
         } = self
         {
-            (name, type_tag, args)
+            (name, type_tag, args.positional())
         } else {
             panic!("Script is expected")
         }
@@ -100,7 +142,7 @@ impl Call {
             args,
         } = self
         {
-            (address, module, func, type_tag, args)
+            (address, module, func, type_tag, args.positional())
         } else {
             panic!("Function is expected")
         }
@@ -109,8 +151,9 @@ impl Call {
 
 /// Parse call
 /// Return: Ok(Script name, Type parameters, Function arguments) or Error
-pub(crate) fn parse_call(addr_map: &AddressDeclarations, call: &str) -> Result<Call, Error> {
-    let mut lexer = Lexer::new(call, FileHash::new("call"));
+pub(crate) fn parse_call(resolver: &AddressResolver, call: &str) -> Result<Call, Error> {
+    let call = resolver.replace_leading_address(call)?;
+    let mut lexer = Lexer::new(&call, FileHash::new("call"));
     let mut env = CompilationEnv::new(Flags::empty(), Default::default());
     let mut ctx = Context::new(&mut env, &mut lexer);
 
@@ -118,13 +161,13 @@ pub(crate) fn parse_call(addr_map: &AddressDeclarations, call: &str) -> Result<C
         .advance()
         .map_err(|err| anyhow!("{}\n\n{:?}", ERROR_MESSAGE, err))?;
 
-    let mut call = parse_call_body(addr_map, &mut ctx)?;
-    call.set_tp_params(parse_type_params(addr_map, &mut ctx)?);
+    let mut call = parse_call_body(resolver, &mut ctx)?;
+    call.set_tp_params(parse_type_params(resolver, &mut ctx)?);
     call.set_args(parse_args(&mut ctx)?);
     Ok(call)
 }
 
-fn parse_call_body(addr_map: &AddressDeclarations, ctx: &mut Context) -> Result<Call, Error> {
+fn parse_call_body(resolver: &AddressResolver, ctx: &mut Context) -> Result<Call, Error> {
     let address = if ctx.tokens.peek() == Tok::NumValue {
         let address = AccountAddress::new(
             parse_address_bytes(ctx)
@@ -168,7 +211,7 @@ fn parse_call_body(addr_map: &AddressDeclarations, ctx: &mut Context) -> Result<
             Call::Script {
                 name: Identifier::new(tokens.remove(0))?,
                 type_tag: vec![],
-                args: vec![],
+                args: CallArgs::default(),
             }
         }
         2 => Call::Function {
@@ -176,7 +219,7 @@ fn parse_call_body(addr_map: &AddressDeclarations, ctx: &mut Context) -> Result<
             module: Identifier::new(tokens.remove(0))?,
             func: Identifier::new(tokens.remove(0))?,
             type_tag: vec![],
-            args: vec![],
+            args: CallArgs::default(),
         },
         3 => {
             if address.is_some() {
@@ -184,9 +227,8 @@ fn parse_call_body(addr_map: &AddressDeclarations, ctx: &mut Context) -> Result<
             }
 
             let named_addr = tokens.remove(0);
-            let addr = addr_map
-                .get(&Symbol::from(named_addr.as_str()))
-                .and_then(|a| *a)
+            let addr = resolver
+                .resolve_name(&named_addr)?
                 .ok_or_else(|| anyhow!("Address {} not found.", named_addr))?;
 
             Call::Function {
@@ -194,7 +236,7 @@ fn parse_call_body(addr_map: &AddressDeclarations, ctx: &mut Context) -> Result<
                 module: Identifier::new(tokens.remove(0))?,
                 func: Identifier::new(tokens.remove(0))?,
                 type_tag: vec![],
-                args: vec![],
+                args: CallArgs::default(),
             }
         }
         _ => {
@@ -204,7 +246,7 @@ fn parse_call_body(addr_map: &AddressDeclarations, ctx: &mut Context) -> Result<
 }
 
 fn parse_type_params(
-    addr_map: &AddressDeclarations,
+    resolver: &AddressResolver,
     ctx: &mut Context,
 ) -> Result<Vec<TypeTag>, Error> {
     let error_message = "Invalid call script format: Invalid type parameters format.\n\n\
@@ -232,7 +274,7 @@ fn parse_type_params(
 
             let type_str = ctx.tokens.content().to_string();
             type_parameter.push(
-                parse_type_param(addr_map, ctx)
+                parse_type_param(resolver, ctx)
                     .map_err(|_| anyhow!("{}\n\nUnknown: {}", &error_message, type_str))?,
             );
         }
@@ -245,11 +287,13 @@ fn parse_type_params(
     }
 }
 
-fn parse_args(ctx: &mut Context) -> Result<Vec<String>, Error> {
+fn parse_args(ctx: &mut Context) -> Result<CallArgs, Error> {
     let error_message = "Invalid call script format: Invalid script arguments format.\n\n\
          Use pattern:\n\
          SCRIPT_FUNCTION_NAME<TYPE1, TYPE2, ...>(PARAM1, PARAM2, ...)\
          or\n\
+         SCRIPT_FUNCTION_NAME<TYPE1, TYPE2, ...>(NAME1: PARAM1, NAME2: PARAM2, ...)\
+         or\n\
          ACCOUNT_ADDRESS::MODULE_NAME::FUNCTION_NAME<TYPE1, TYPE2, ...>(PARAM1, PARAM2, ...)";
     let map_err = |_| anyhow!("{}", &error_message);
 
@@ -268,37 +312,78 @@ fn parse_args(ctx: &mut Context) -> Result<Vec<String>, Error> {
                 continue;
             }
 
+            // Named argument: NAME: PARAM
+            let name = if ctx.tokens.peek() == Tok::Identifier
+                && ctx.tokens.lookahead().map_err(map_err)? == Tok::Colon
+            {
+                let name = ctx.tokens.content().to_string();
+                ctx.tokens.advance().map_err(map_err)?;
+                ctx.tokens.advance().map_err(map_err)?;
+                Some(name)
+            } else {
+                None
+            };
+
             let mut token = String::new();
             token.push_str(ctx.tokens.content());
             let sw = ctx.tokens.peek() == Tok::LBracket;
             ctx.tokens.advance().map_err(map_err)?;
             if sw {
-                while ctx.tokens.peek() != Tok::RBracket {
+                // Vectors can be nested: [[1, 2], [3]]
+                let mut depth = 1;
+                loop {
+                    match ctx.tokens.peek() {
+                        Tok::LBracket => depth += 1,
+                        Tok::RBracket => depth -= 1,
+                        Tok::EOF => anyhow::bail!("{}", &error_message),
+                        _ => {}
+                    }
                     token.push_str(ctx.tokens.content());
+                    if depth == 0 {
+                        break;
+                    }
                     ctx.tokens
                         .advance()
                         .map_err(|_| anyhow!("{}", &error_message))?;
                 }
-                token.push_str(ctx.tokens.content());
             } else {
                 while ctx.tokens.peek() != Tok::Comma && ctx.tokens.peek() != Tok::RParen {
+                    if ctx.tokens.peek() == Tok::EOF {
+                        anyhow::bail!("{}", &error_message);
+                    }
                     token.push_str(ctx.tokens.content());
                     ctx.tokens.advance().map_err(map_err)?;
                 }
             }
-            arguments.push(token);
+            arguments.push((name, token));
             if !sw && ctx.tokens.peek() == Tok::RParen {
                 break;
             }
             ctx.tokens.advance().map_err(map_err)?;
         }
-        Ok(arguments)
+
+        if arguments.iter().all(|(name, _)| name.is_none()) {
+            Ok(CallArgs::Positional(
+                arguments.into_iter().map(|(_, arg)| arg).collect(),
+            ))
+        } else if arguments.iter().all(|(name, _)| name.is_some()) {
+            Ok(CallArgs::Named(
+                arguments
+                    .into_iter()
+                    .filter_map(|(name, arg)| name.map(|name| (name, arg)))
+                    .collect(),
+            ))
+        } else {
+            anyhow::bail!(
+                "Invalid call script format: named and positional arguments cannot be mixed."
+            )
+        }
     } else {
-        Ok(vec![])
+        Ok(CallArgs::default())
     }
 }
 
-pub(crate) fn parse_tp_param(addr_map: &AddressDeclarations, tp: &str) -> Result<TypeTag, Error> {
+pub(crate) fn parse_tp_param(resolver: &AddressResolver, tp: &str) -> Result<TypeTag, Error> {
     let mut lexer = Lexer::new(tp, FileHash::new("tp"));
     let mut env = CompilationEnv::new(Flags::empty(), Default::default());
     let mut ctx = Context::new(&mut env, &mut lexer);
@@ -306,7 +391,7 @@ pub(crate) fn parse_tp_param(addr_map: &AddressDeclarations, tp: &str) -> Result
     ctx.tokens
         .advance()
         .map_err(|err| Error::msg(format!("{:?}", err)))?;
-    parse_type_param(addr_map, &mut ctx)
+    parse_type_param(resolver, &mut ctx)
 }
 
 /// parse type params
@@ -315,11 +400,11 @@ pub(crate) fn parse_tp_param(addr_map: &AddressDeclarations, tp: &str) -> Result
 /// u64 => TypeTag::U64
 /// ...
 pub(crate) fn parse_type_param(
-    addr_map: &AddressDeclarations,
+    resolver: &AddressResolver,
     ctx: &mut Context,
 ) -> Result<TypeTag, Error> {
     let ty = parse_type(ctx).map_err(|err| Error::msg(format!("{:?}", err)))?;
-    unwrap_spanned_ty(addr_map, ty)
+    unwrap_spanned_ty(resolver, ty)
 }
 
 pub(crate) fn parse_vec<E>(tkn: &str, tp_name: &str) -> Result<Vec<E>, Error>
@@ -362,6 +447,51 @@ where
     Ok(elements)
 }
 
+/// Splits a vector into its top-level elements.
+///
+/// [[1, 2], 0102, []] => ["[1,2]", "0102", "[]"]
+pub(crate) fn split_vec(tkn: &str) -> Result<Vec<String>, Error> {
+    let inner = tkn
+        .trim()
+        .strip_prefix('[')
+        .and_then(|tkn| tkn.strip_suffix(']'))
+        .ok_or_else(|| anyhow!("Vector in format  [n1, n2, ..., nn] is expected."))?;
+
+    let mut elements = vec![];
+    let mut element = String::new();
+    let mut depth = 0;
+    for ch in inner.chars() {
+        match ch {
+            '[' => depth += 1,
+            ']' => {
+                if depth == 0 {
+                    anyhow::bail!("Unexpected ']' in vector '{}'.", tkn);
+                }
+                depth -= 1;
+            }
+            ',' if depth == 0 => {
+                if element.is_empty() {
+                    anyhow::bail!("Empty vector element in '{}'.", tkn);
+                }
+                elements.push(std::mem::take(&mut element));
+                continue;
+            }
+            _ => {}
+        }
+        if !ch.is_whitespace() {
+            element.push(ch);
+        }
+    }
+
+    if depth != 0 {
+        anyhow::bail!("unexpected end of vector.");
+    }
+    if !element.is_empty() {
+        elements.push(element);
+    }
+    Ok(elements)
+}
+
 #[cfg(test)]
 mod tests_call_parser {
     use std::collections::BTreeMap;
@@ -370,7 +500,8 @@ mod tests_call_parser {
     use move_core_types::language_storage::{StructTag, TypeTag};
     use move_core_types::language_storage::CORE_CODE_ADDRESS;
     use move_symbol_pool::Symbol;
-    use crate::call::parser::parse_call;
+    use lang::address::AddressResolver;
+    use crate::call::parser::{parse_call, split_vec};
 
     #[test]
     fn func_call() {
@@ -515,6 +646,73 @@ mod tests_call_parser {
         assert_eq!(args, Vec::<String>::new());
     }
 
+    #[test]
+    fn nested_vector_args() {
+        let (name, _, args) = parse_call(
+            &Default::default(),
+            "create_account([[1, 2], [3]], [0102, [1, 2], []], 10)",
+        )
+        .unwrap()
+        .script();
+        assert_eq!(name.as_str(), "create_account");
+        assert_eq!(
+            args,
+            vec![
+                "[[1,2],[3]]".to_owned(),
+                "[0102,[1,2],[]]".to_owned(),
+                "10".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn named_args() {
+        let args = parse_call(
+            &Default::default(),
+            "0x1::Coins::transfer<u8>(to: 0x2, amount: 100, data: [[1, 2], 0102])",
+        )
+        .unwrap()
+        .args()
+        .named();
+        assert_eq!(
+            args,
+            vec![
+                ("to".to_owned(), "0x2".to_owned()),
+                ("amount".to_owned(), "100".to_owned()),
+                ("data".to_owned(), "[[1,2],0102]".to_owned()),
+            ]
+        );
+
+        let args = parse_call(&Default::default(), "transfer(to: Std, amount: 100,)")
+            .unwrap()
+            .args()
+            .named();
+        assert_eq!(
+            args,
+            vec![
+                ("to".to_owned(), "Std".to_owned()),
+                ("amount".to_owned(), "100".to_owned()),
+            ]
+        );
+
+        assert!(parse_call(&Default::default(), "transfer(to: 0x2, 100)").is_err());
+        assert!(parse_call(&Default::default(), "transfer(0x2, amount: 100)").is_err());
+        assert!(parse_call(&Default::default(), "transfer(to: 0x2").is_err());
+    }
+
+    #[test]
+    fn test_split_vec() {
+        assert_eq!(split_vec("[]").unwrap(), Vec::<String>::new());
+        assert_eq!(split_vec("[1, 2,]").unwrap(), vec!["1", "2"]);
+        assert_eq!(
+            split_vec("[[1, 2], 0102, [], [[0x1]]]").unwrap(),
+            vec!["[1,2]", "0102", "[]", "[[0x1]]"]
+        );
+        assert!(split_vec("1, 2").is_err());
+        assert!(split_vec("[[1, 2]").is_err());
+        assert!(split_vec("[1,, 2]").is_err());
+    }
+
     #[test]
     fn named_address() {
         let mut map = BTreeMap::new();
@@ -522,10 +720,12 @@ mod tests_call_parser {
             Symbol::from("Core"),
             Some(AccountAddress::from_hex_literal("0x13").unwrap()),
         );
-        let (address, name, func, type_tag, args) =
-            parse_call(&map, "Core::Diem::create_account(Std)")
-                .unwrap()
-                .func();
+        let (address, name, func, type_tag, args) = parse_call(
+            &AddressResolver::new(map),
+            "Core::Diem::create_account(Std)",
+        )
+        .unwrap()
+        .func();
         assert_eq!(
             address,
             Some(AccountAddress::from_hex_literal("0x13").unwrap())
@@ -544,6 +744,30 @@ mod tests_call_parser {
             Symbol::from("CoRe"),
             Some(AccountAddress::from_hex_literal("0x13").unwrap()),
         );
-        parse_call(&map, "Core::Diem::create_account(Std)").unwrap();
+        parse_call(
+            &AddressResolver::new(map),
+            "Core::Diem::create_account(Std)",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn leading_address() {
+        let alice = AccountAddress::from_hex_literal(
+            "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+        )
+        .unwrap();
+        for call in [
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY::Store::store(1)",
+            "//Alice::Store::store(1)",
+            "alice::Store::store(1)",
+        ] {
+            let (address, name, func, _, args) =
+                parse_call(&Default::default(), call).unwrap().func();
+            assert_eq!(address, Some(alice));
+            assert_eq!(name.as_str(), "Store");
+            assert_eq!(func.as_str(), "store");
+            assert_eq!(args, vec!["1".to_string()]);
+        }
     }
 }
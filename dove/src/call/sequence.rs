@@ -0,0 +1,136 @@
+use std::path::Path;
+use anyhow::Error;
+use serde::Deserialize;
+use serde_json::Value;
+use lang::address::AddressResolver;
+use crate::call::args_file::{read_file, value_to_args};
+use crate::call::cmd::CallDeclaration;
+use crate::call::parser::{parse_call, parse_tp_param};
+
+/// Call sequence file.
+///
+/// TOML:
+///     [[call]]
+///     call = "Config::publish_config(100)"
+///
+///     [[call]]
+///     call = "Coins::mint"
+///     args = { to = "alice", amount = 100 }
+///     type_args = ["0x1::PONT::PONT"]
+///
+/// JSON:
+///     { "call": [{ "call": "init()" }, { "call": "mint", "args": ["alice", 100] }] }
+#[derive(Deserialize, Debug)]
+struct SequenceFile {
+    #[serde(rename = "call")]
+    calls: Vec<SequenceCall>,
+}
+
+/// Call of the sequence.
+#[derive(Deserialize, Debug)]
+struct SequenceCall {
+    /// Call declaration.
+    call: String,
+    /// Positional (array) or named (object) arguments.
+    args: Option<Value>,
+    /// Type arguments.
+    type_args: Option<Vec<String>>,
+    /// Move package name.
+    package: Option<String>,
+}
+
+/// Loads the call declarations from a JSON or TOML sequence file.
+pub(crate) fn load_sequence(
+    resolver: &AddressResolver,
+    path: &Path,
+) -> Result<Vec<CallDeclaration>, Error> {
+    let sequence: SequenceFile = serde_json::from_value(read_file(path)?)
+        .map_err(|err| anyhow!("Failed to parse sequence {:?}: {}", path, err))?;
+    ensure!(
+        !sequence.calls.is_empty(),
+        "The sequence {:?} is empty",
+        path
+    );
+
+    sequence
+        .calls
+        .into_iter()
+        .enumerate()
+        .map(|(index, call)| {
+            make_declaration(resolver, call)
+                .map_err(|err| anyhow!("Call {} in {:?}: {}", index + 1, path, err))
+        })
+        .collect()
+}
+
+fn make_declaration(
+    resolver: &AddressResolver,
+    sequence_call: SequenceCall,
+) -> Result<CallDeclaration, Error> {
+    let mut call = parse_call(resolver, &sequence_call.call)?;
+    if let Some(args) = &sequence_call.args {
+        call.set_args(value_to_args(args)?);
+    }
+    if let Some(tp) = &sequence_call.type_args {
+        call.set_tp_params(
+            tp.iter()
+                .map(|tp| parse_tp_param(resolver, tp))
+                .collect::<Result<_, _>>()?,
+        );
+    }
+
+    Ok(CallDeclaration {
+        call,
+        package: sequence_call.package,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use crate::call::parser::CallArgs;
+    use super::load_sequence;
+
+    #[test]
+    fn test_load_sequence() {
+        let dir = std::env::temp_dir().join("dove_test_load_sequence");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("calls.toml");
+        fs::write(
+            &path,
+            r#"
+                [[call]]
+                call = "main()"
+
+                [[call]]
+                call = "two_params"
+                args = [1, 2]
+
+                [[call]]
+                call = "with_type"
+                args = { _a = 1 }
+                type_args = ["u8"]
+                package = "for_tests"
+            "#,
+        )
+        .unwrap();
+
+        let mut calls = load_sequence(&Default::default(), &path).unwrap();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[2].package.as_deref(), Some("for_tests"));
+        assert_eq!(calls.remove(0).call.args(), CallArgs::Positional(vec![]));
+        assert_eq!(
+            calls.remove(0).call.args(),
+            CallArgs::Positional(vec!["1".to_string(), "2".to_string()])
+        );
+        assert_eq!(
+            calls.remove(0).call.args(),
+            CallArgs::Named(vec![("_a".to_string(), "1".to_string())])
+        );
+
+        fs::write(&path, "call = []").unwrap();
+        assert!(load_sequence(&Default::default(), &path).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use std::fs;
+use anyhow::Error;
+use url::Url;
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::CompiledModule;
+use move_cli::DEFAULT_STORAGE_DIR;
+use move_cli::sandbox::utils::on_disk_state_view::OnDiskStateView;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::effects::ChangeSet;
+use move_core_types::gas_schedule::{GasAlgebra, GasUnits};
+use move_core_types::language_storage::{ModuleId, StructTag};
+use move_core_types::resolver::{ModuleResolver, ResourceResolver};
+use move_package::compilation::package_layout::CompiledPackageLayout;
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_types::gas_schedule::GasStatus;
+use net::{make_cached_net, CacheConfig, NetView};
+use lang::address::AddressResolver;
+use crate::context::Context;
+use crate::call::fn_call::diem_root_address;
+use crate::call::model::{Call, Signer, Transaction};
+use crate::cmd::deploy::get_bytecode_modules_path;
+
+/// Gas budget of the simulation if the gas limit is not specified.
+pub const DEFAULT_GAS_BUDGET: u64 = 1_000_000_000;
+
+/// State on which the transaction is simulated.
+pub enum State {
+    /// Local sandbox storage.
+    ///     ./storage
+    Sandbox,
+    /// State of the node. Without the cache config the modules are fetched from the node.
+    Fork(Url, Option<CacheConfig>),
+}
+
+/// Result of the transaction simulation.
+pub struct Simulation {
+    /// Gas consumed by the transaction.
+    pub gas_used: u64,
+    /// Execution error if the transaction failed.
+    pub error: Option<String>,
+    /// Changes made by the transaction.
+    pub change_set: ChangeSet,
+    /// Number of emitted events.
+    pub events: usize,
+}
+
+impl Simulation {
+    /// Returns true if the transaction was executed successfully.
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Prints the simulation report.
+    pub fn print(&self) {
+        println!("Simulation:");
+        match &self.error {
+            Some(error) => println!("    Status: failed\n    Error: {}", error),
+            None => println!("    Status: executed"),
+        }
+        println!("    Gas used: {}", self.gas_used);
+        println!("    Events: {}", self.events);
+
+        if self.change_set.accounts.is_empty() {
+            println!("    Write set: empty");
+            return;
+        }
+        println!("    Write set:");
+        for (address, changes) in &self.change_set.accounts {
+            println!("        {}:", address.to_hex_literal());
+            for (name, module) in &changes.modules {
+                match module {
+                    Some(bytecode) => {
+                        println!(
+                            "            write module {} ({} bytes)",
+                            name,
+                            bytecode.len()
+                        )
+                    }
+                    None => println!("            delete module {}", name),
+                }
+            }
+            for (tag, resource) in &changes.resources {
+                match resource {
+                    Some(value) => println!("            write {} ({} bytes)", tag, value.len()),
+                    None => println!("            delete {}", tag),
+                }
+            }
+        }
+    }
+}
+
+/// Executes the transactions one after another in the local VM with the Pontem cost table.
+/// Each transaction sees the changes of the previous ones.
+/// The execution stops at the first failed transaction.
+/// Modules of the project and its dependencies override the modules from the state.
+pub fn simulate(
+    ctx: &Context,
+    txs: &[Transaction],
+    state: &State,
+    sender: AccountAddress,
+    gas_budget: u64,
+) -> Result<Vec<Simulation>, Error> {
+    let modules = project_modules(ctx)?;
+    match state {
+        State::Sandbox => {
+            let storage = OnDiskStateView::create(
+                ctx.path_for_build(None, CompiledPackageLayout::Root),
+                ctx.project_root_dir.join(DEFAULT_STORAGE_DIR),
+            )?;
+            execute(ctx, Overlay::new(modules, storage), txs, sender, gas_budget)
+        }
+        State::Fork(url, cache) => {
+            let net = make_cached_net(url.clone(), cache.clone())?;
+            let base = NetView::new(net, None);
+            execute(ctx, Overlay::new(modules, base), txs, sender, gas_budget)
+        }
+    }
+}
+
+fn execute<S>(
+    ctx: &Context,
+    mut state: Overlay<S>,
+    txs: &[Transaction],
+    sender: AccountAddress,
+    gas_budget: u64,
+) -> Result<Vec<Simulation>, Error>
+where
+    S: ModuleResolver<Error = Error> + ResourceResolver<Error = Error>,
+{
+    let vm = MoveVM::new(ctx.native_functions.clone())
+        .map_err(|err| anyhow!("Failed to create the VM: {:?}", err))?;
+    let resolver = ctx.address_resolver();
+
+    let mut simulations = Vec::with_capacity(txs.len());
+    for tx in txs {
+        let senders = senders(tx, sender, &resolver)?;
+        let simulation = execute_tx(ctx, &vm, &state, tx, senders, gas_budget);
+        state.apply(&simulation.change_set);
+        let is_success = simulation.is_success();
+        simulations.push(simulation);
+        if !is_success {
+            break;
+        }
+    }
+    Ok(simulations)
+}
+
+fn execute_tx<S>(
+    ctx: &Context,
+    vm: &MoveVM,
+    state: &S,
+    tx: &Transaction,
+    senders: Vec<AccountAddress>,
+    gas_budget: u64,
+) -> Simulation
+where
+    S: ModuleResolver<Error = Error> + ResourceResolver<Error = Error>,
+{
+    let Transaction::V1(tx) = tx;
+    let mut gas_status = GasStatus::new(&ctx.cost_table, GasUnits::new(gas_budget));
+    let mut session = vm.new_session(state);
+
+    let result = match &tx.call {
+        Call::Script { code } => session.execute_script(
+            code.to_owned(),
+            tx.type_args.clone(),
+            tx.args.clone(),
+            senders,
+            &mut gas_status,
+        ),
+        Call::ScriptFunction {
+            mod_address,
+            mod_name,
+            func_name,
+        } => session.execute_script_function(
+            &ModuleId::new(*mod_address, mod_name.to_owned()),
+            func_name,
+            tx.type_args.clone(),
+            tx.args.clone(),
+            senders,
+            &mut gas_status,
+        ),
+    }
+    .and_then(|_| session.finish());
+    let gas_used = gas_budget - gas_status.remaining_gas().get();
+
+    match result {
+        Ok((change_set, events)) => Simulation {
+            gas_used,
+            error: None,
+            change_set,
+            events: events.len(),
+        },
+        Err(err) => Simulation {
+            gas_used,
+            error: Some(format!("{:?}", err.into_vm_status())),
+            change_set: ChangeSet::new(),
+            events: 0,
+        },
+    }
+}
+
+/// Replaces the transaction signers with addresses.
+/// Placeholders are replaced by the sender.
+fn senders(
+    tx: &Transaction,
+    sender: AccountAddress,
+    resolver: &AddressResolver,
+) -> Result<Vec<AccountAddress>, Error> {
+    let Transaction::V1(tx) = tx;
+    tx.signers
+        .iter()
+        .map(|signer| match signer {
+            Signer::Root => Ok(diem_root_address()),
+            Signer::Placeholder => Ok(sender),
+            Signer::Name(name) => resolver.resolve_account(name.as_str()),
+        })
+        .collect()
+}
+
+/// Returns the compiled modules of the project and its dependencies.
+///     ./build/<package>/bytecode_modules/**/*.mv
+pub(crate) fn project_modules(
+    ctx: &Context,
+) -> Result<HashMap<ModuleId, Option<Vec<u8>>>, Error> {
+    get_bytecode_modules_path(&ctx.project_root_dir, &ctx.manifest.package.name)?
+        .into_iter()
+        .map(|path| {
+            let bytecode = fs::read(&path)?;
+            let module = CompiledModule::deserialize(&bytecode)
+                .map_err(|err| anyhow!("Failed to deserialize module {:?}: {:?}", path, err))?;
+            Ok((module.self_id(), Some(bytecode)))
+        })
+        .collect()
+}
+
+/// State with the project modules and the simulated changes on top of the base state.
+struct Overlay<S> {
+    modules: HashMap<ModuleId, Option<Vec<u8>>>,
+    resources: HashMap<(AccountAddress, StructTag), Option<Vec<u8>>>,
+    base: S,
+}
+
+impl<S> Overlay<S> {
+    fn new(modules: HashMap<ModuleId, Option<Vec<u8>>>, base: S) -> Overlay<S> {
+        Overlay {
+            modules,
+            resources: HashMap::new(),
+            base,
+        }
+    }
+
+    /// Applies the changes of the executed transaction.
+    fn apply(&mut self, change_set: &ChangeSet) {
+        for (address, changes) in &change_set.accounts {
+            for (name, module) in &changes.modules {
+                self.modules
+                    .insert(ModuleId::new(*address, name.to_owned()), module.to_owned());
+            }
+            for (tag, resource) in &changes.resources {
+                self.resources
+                    .insert((*address, tag.to_owned()), resource.to_owned());
+            }
+        }
+    }
+}
+
+impl<S> ModuleResolver for Overlay<S>
+where
+    S: ModuleResolver<Error = Error>,
+{
+    type Error = Error;
+
+    fn get_module(&self, module_id: &ModuleId) -> Result<Option<Vec<u8>>, Error> {
+        match self.modules.get(module_id) {
+            Some(bytecode) => Ok(bytecode.to_owned()),
+            None => self.base.get_module(module_id),
+        }
+    }
+}
+
+impl<S> ResourceResolver for Overlay<S>
+where
+    S: ResourceResolver<Error = Error>,
+{
+    type Error = Error;
+
+    fn get_resource(
+        &self,
+        address: &AccountAddress,
+        tag: &StructTag,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        match self.resources.get(&(*address, tag.to_owned())) {
+            Some(resource) => Ok(resource.to_owned()),
+            None => self.base.get_resource(address, tag),
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use std::fmt;
+use anyhow::Error;
+use move_core_types::account_address::AccountAddress;
+use lang::bytecode::accessor::BytecodeType;
+use lang::bytecode::{find, SearchParams};
+use lang::bytecode::info::{BytecodeInfo, Script};
+use crate::context::Context;
+use crate::call::bytecode::{DoveBytecode, package_name, parameter_names};
+
+/// Maximum number of suggestions shown for an unresolved call.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Called function.
+pub(crate) enum Target<'a> {
+    /// Script name.
+    Script(&'a str),
+    /// Script function.
+    Function {
+        address: Option<AccountAddress>,
+        module: &'a str,
+        func: &'a str,
+    },
+}
+
+impl<'a> Target<'a> {
+    fn bytecode_type(&self) -> BytecodeType {
+        match self {
+            Target::Script(_) => BytecodeType::Script,
+            Target::Function { .. } => BytecodeType::Module,
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Target::Script(name) => name.to_string(),
+            Target::Function { module, func, .. } => format!("{}::{}", module, func),
+        }
+    }
+}
+
+impl<'a> fmt::Display for Target<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Script(name) => write!(f, "script '{}'", name),
+            Target::Function {
+                address: Some(address),
+                module,
+                func,
+            } => write!(
+                f,
+                "function '{}::{}::{}'",
+                address.to_hex_literal(),
+                module,
+                func
+            ),
+            Target::Function {
+                address: None,
+                module,
+                func,
+            } => write!(f, "function '{}::{}'", module, func),
+        }
+    }
+}
+
+/// The call did not resolve to any function.
+#[derive(Debug, Default)]
+pub(crate) struct Unresolved {
+    /// Functions with the requested name and the reasons they were rejected.
+    pub rejected: Vec<(String, String)>,
+}
+
+impl Unresolved {
+    /// Adds the rejected function.
+    pub fn reject(&mut self, info: &BytecodeInfo, script: &Script, reason: String) {
+        self.rejected.push((signature(info, script), reason));
+    }
+
+    /// Explains why the call was not resolved.
+    /// Lists the rejected functions and the functions with similar names from all packages.
+    pub fn explain(self, ctx: &Context, target: &Target) -> Error {
+        let mut message = format!("Couldn't find a function with given signature: {}.", target);
+
+        if !self.rejected.is_empty() {
+            message.push_str("\nRejected candidates:");
+            for (signature, reason) in &self.rejected {
+                message.push_str(&format!("\n    {}\n        {}", signature, reason));
+            }
+        }
+
+        let suggestions = suggestions(ctx, target)
+            .into_iter()
+            .filter(|signature| !self.rejected.iter().any(|(s, _)| s == signature))
+            .take(MAX_SUGGESTIONS)
+            .collect::<Vec<_>>();
+        if !suggestions.is_empty() {
+            message.push_str("\nDid you mean:");
+            for signature in suggestions {
+                message.push_str(&format!("\n    {}", signature));
+            }
+        }
+
+        anyhow!(message)
+    }
+}
+
+impl fmt::Display for Unresolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Couldn't find a function with given signature.")?;
+        for (signature, reason) in &self.rejected {
+            write!(f, "\n    {}\n        {}", signature, reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Unresolved {}
+
+/// Replaces the [Unresolved] error with a detailed explanation.
+pub(crate) fn explain(err: Error, ctx: &Context, target: &Target) -> Error {
+    match err.downcast::<Unresolved>() {
+        Ok(unresolved) => unresolved.explain(ctx, target),
+        Err(err) => err,
+    }
+}
+
+/// Returns the signatures of all callable functions ordered by name similarity to the target.
+fn suggestions(ctx: &Context, target: &Target) -> Vec<String> {
+    let bytecode = match find(
+        DoveBytecode::new(ctx),
+        SearchParams {
+            tp: Some(target.bytecode_type()),
+            package: None,
+            name: None,
+        },
+    ) {
+        Ok(bytecode) => bytecode,
+        Err(_) => return vec![],
+    };
+
+    let name = target.name();
+    let mut candidates = bytecode
+        .filter_map(|info| info.ok())
+        .flat_map(|info| {
+            info.script_functions()
+                .into_iter()
+                .map(|script| {
+                    let candidate_name = if info.is_module() {
+                        format!("{}::{}", info.name(), script.name)
+                    } else {
+                        script.name.clone()
+                    };
+                    (distance(&name, &candidate_name), signature(&info, &script))
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(|(distance, _)| *distance <= name.len().max(3) / 2 + 1)
+        .collect::<Vec<_>>();
+    candidates.sort();
+    candidates.dedup();
+    candidates
+        .into_iter()
+        .map(|(_, signature)| signature)
+        .collect()
+}
+
+/// Returns the function signature with the parameter names and the type parameter abilities.
+///     0x2::Module::func<T0: copy + drop>(account: signer, amount: u64) [package]
+fn signature(info: &BytecodeInfo, script: &Script) -> String {
+    let names = parameter_names(info, &script.name).ok();
+    let parameters = script
+        .parameters
+        .iter()
+        .enumerate()
+        .map(
+            |(index, tp)| match names.as_ref().and_then(|names| names.get(index)) {
+                Some(name) => format!("{}: {}", name, tp),
+                None => tp.to_string(),
+            },
+        )
+        .collect::<Vec<_>>();
+
+    let mut signature = match info.address() {
+        Some(address) if info.is_module() => {
+            format!(
+                "{}::{}::{}",
+                address.to_hex_literal(),
+                info.name(),
+                script.name
+            )
+        }
+        _ => script.name.clone(),
+    };
+
+    if !script.type_parameters.is_empty() {
+        let type_parameters = script
+            .type_parameters
+            .iter()
+            .enumerate()
+            .map(|(index, abilities)| {
+                if abilities.abilities.is_empty() {
+                    format!("T{}", index)
+                } else {
+                    format!("T{}: {}", index, abilities)
+                }
+            })
+            .collect::<Vec<_>>();
+        signature.push_str(&format!("<{}>", type_parameters.join(", ")));
+    }
+    signature.push_str(&format!("({})", parameters.join(", ")));
+
+    if let Some(package) = package_name(info.bytecode_ref()) {
+        signature.push_str(&format!(" [{}]", package));
+    }
+    signature
+}
+
+/// Case-insensitive Levenshtein distance.
+fn distance(left: &str, right: &str) -> usize {
+    let left = left.to_lowercase().chars().collect::<Vec<_>>();
+    let right = right.to_lowercase().chars().collect::<Vec<_>>();
+
+    let mut row = (0..=right.len()).collect::<Vec<_>>();
+    for (i, l) in left.iter().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, r) in right.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if l == r {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(current)
+            };
+            prev = current;
+        }
+    }
+    row[right.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::distance;
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("main", "main"), 0);
+        assert_eq!(distance("Main", "main"), 0);
+        assert_eq!(distance("two_param", "two_params"), 1);
+        assert_eq!(distance("one_parm", "one_param"), 1);
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("", "abc"), 3);
+        assert_eq!(distance("Demo::hello", "Demo::hallo"), 1);
+    }
+}
@@ -11,11 +11,17 @@ use move_core_types::errmap::ErrorMapping;
 use crate::{
     DOVE_VERSION, DOVE_HASH, MOVE_STDLIB_VERSION, DIEM_VERSION, DIEM_HASH, ERROR_DESCRIPTIONS,
 };
+use crate::cmd::address::Address;
 use crate::cmd::clean::Clean;
 use crate::cmd::run::Run;
 use crate::cmd::call::ExecuteTransaction;
+use crate::cmd::check_compat::CheckCompat;
 use crate::cmd::key::Key;
 use crate::cmd::deploy::Deploy;
+use crate::cmd::deployments::Deployments;
+use crate::cmd::inspect::Inspect;
+use crate::cmd::pipeline::Pipeline;
+use crate::cmd::verify::Verify;
 use crate::cmd::view::View;
 use crate::context::Context;
 use crate::natives::{all_natives, pontem_cost_table};
@@ -86,6 +92,43 @@ pub enum DoveCommands {
         #[structopt(flatten)]
         cmd: Deploy,
     },
+    #[structopt(
+        about = "Check the upgrade compatibility of the modules with the previous version",
+        display_order = 20
+    )]
+    CheckCompat {
+        #[structopt(flatten)]
+        cmd: CheckCompat,
+    },
+    #[structopt(
+        about = "Inspect the bundle or the module. Alias for 'package inspect'",
+        display_order = 21
+    )]
+    Inspect {
+        #[structopt(flatten)]
+        cmd: Inspect,
+    },
+    #[structopt(
+        about = "Verify the deployed modules against the project sources",
+        display_order = 22
+    )]
+    Verify {
+        #[structopt(flatten)]
+        cmd: Verify,
+    },
+    #[structopt(
+        about = "Run the deployment pipeline against the network",
+        display_order = 23
+    )]
+    Pipeline {
+        #[structopt(flatten)]
+        cmd: Pipeline,
+    },
+    #[structopt(about = "Deployment history of the project")]
+    Deployments {
+        #[structopt(flatten)]
+        cmd: Deployments,
+    },
     #[structopt(about = "Manage wallet keys")]
     Key {
         #[structopt(flatten)]
@@ -96,6 +139,11 @@ pub enum DoveCommands {
         #[structopt(flatten)]
         cmd: View,
     },
+    #[structopt(about = "Convert addresses between hex and SS58 formats")]
+    Address {
+        #[structopt(flatten)]
+        cmd: Address,
+    },
 }
 
 fn preprocess_args(args: Vec<String>) -> Vec<String> {
@@ -105,6 +153,10 @@ fn preprocess_args(args: Vec<String>) -> Vec<String> {
     line = line.replace(&format!("{dove} build"), &format!("{dove} package build"));
     line = line.replace(&format!("{dove} test"), &format!("{dove} package test"));
     line = line.replace(&format!("{dove} prove"), &format!("{dove} package prove"));
+    line = line.replace(
+        &format!("{dove} package inspect"),
+        &format!("{dove} inspect"),
+    );
     line = line.replace(
         &format!("{dove} init"),
         &format!("{dove} package new --cwd"),
@@ -120,13 +172,16 @@ pub fn execute(args: Vec<String>, cwd: PathBuf) -> Result<()> {
     let args = preprocess_args(args);
     let DoveOpt { move_args, cmd } = DoveOpt::from_iter(args);
 
-    // `dove clean`|`dove key` needs empty context and no preparation, so try it before other commands
+    // `dove clean`|`dove key`|`dove address`|`dove inspect`|`dove deployments` needs empty context and no preparation, so try it before other commands
     match cmd {
         DoveCommands::Clean { mut cmd } => {
             cmd.apply(&cwd);
             return Ok(());
         }
         DoveCommands::Key { mut cmd } => return cmd.apply(),
+        DoveCommands::Address { mut cmd } => return cmd.apply(&cwd),
+        DoveCommands::Inspect { mut cmd } => return cmd.apply(&cwd),
+        DoveCommands::Deployments { mut cmd } => return cmd.apply(&cwd),
         _ => (),
     };
 
@@ -158,6 +213,9 @@ pub fn execute(args: Vec<String>, cwd: PathBuf) -> Result<()> {
         DoveCommands::Call { mut cmd } => cmd.apply(&mut ctx),
         DoveCommands::Deploy { mut cmd } => cmd.apply(&mut ctx),
         DoveCommands::View { mut cmd } => cmd.apply(&mut ctx),
+        DoveCommands::CheckCompat { mut cmd } => cmd.apply(&mut ctx),
+        DoveCommands::Verify { mut cmd } => cmd.apply(&mut ctx),
+        DoveCommands::Pipeline { mut cmd } => cmd.apply(&mut ctx),
         DoveCommands::Build
         | DoveCommands::Test
         | DoveCommands::Prove
@@ -165,7 +223,12 @@ pub fn execute(args: Vec<String>, cwd: PathBuf) -> Result<()> {
         | DoveCommands::Init => {
             unreachable!("Should never be reached, as all those commands are preprocessed into package-prefixed commands")
         }
-        DoveCommands::Clean { .. } | DoveCommands::DiemCommand(_) | DoveCommands::Key { .. } => {
+        DoveCommands::Clean { .. }
+        | DoveCommands::DiemCommand(_)
+        | DoveCommands::Key { .. }
+        | DoveCommands::Address { .. }
+        | DoveCommands::Inspect { .. }
+        | DoveCommands::Deployments { .. } => {
             unreachable!("Handled in the beginning")
         }
     }
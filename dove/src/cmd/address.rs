@@ -0,0 +1,192 @@
+use std::path::Path;
+use anyhow::Result;
+use structopt::StructOpt;
+use move_core_types::account_address::AccountAddress;
+
+use lang::address::{AddressResolver, DEV_ACCOUNTS, dev_account};
+use lang::ss58::{
+    address_to_ss58_with_prefix, decode_ss58, network_prefix, DEFAULT_SS58_PREFIX, SS58_NETWORKS,
+};
+use crate::context::read_manifest;
+use crate::publish::wallet_key_address;
+
+/// Address tools
+#[derive(StructOpt, Debug)]
+#[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
+#[structopt(usage = "dove address [SUBCOMMAND]
+    Examples:
+    $ dove address convert 0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d
+    $ dove address convert //Alice --prefix pontem
+    $ dove address convert 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY --prefix 105
+    $ dove address convert gkQ5K6EnLRgZkwozG8GiBAEnJyM6FxzbSaSmVhKJ2w8FcK7ih --hex
+    $ dove address info ADDRESS_ALIAS
+    $ dove address dev
+")]
+pub enum Address {
+    /// Convert the address between hex and SS58 formats
+    #[structopt(name = "convert")]
+    #[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
+    Convert {
+        /// Hex or SS58 address, address alias, wallet key or dev account name
+        address: String,
+
+        /// SS58 network prefix or network name: pontem, polkadot, kusama, substrate, 105...
+        /// Defaults to "ss58_prefix" from the dove section of Move.toml or 42
+        #[structopt(long)]
+        prefix: Option<String>,
+
+        /// Convert to the hex format
+        #[structopt(long, conflicts_with = "prefix")]
+        hex: bool,
+    },
+
+    /// Show the address in all formats and its known names
+    #[structopt(name = "info")]
+    #[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
+    Info {
+        /// Hex or SS58 address, address alias, wallet key or dev account name
+        address: String,
+    },
+
+    /// List of the development accounts
+    #[structopt(name = "dev")]
+    #[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
+    Dev {
+        /// SS58 network prefix or network name
+        #[structopt(long)]
+        prefix: Option<String>,
+    },
+}
+
+impl Address {
+    /// Works without a project.
+    /// Address aliases and the default prefix are taken from Move.toml if it exists.
+    pub fn apply(&mut self, project_dir: &Path) -> Result<()> {
+        let resolver = resolver(project_dir);
+        match self {
+            Address::Convert {
+                address,
+                prefix,
+                hex,
+            } => {
+                let (account, ss58_prefix) = parse(&resolver, address)?;
+                let converted = if *hex || (ss58_prefix.is_some() && prefix.is_none()) {
+                    account.to_hex_literal()
+                } else {
+                    address_to_ss58_with_prefix(&account, self::prefix(project_dir, prefix)?)?
+                };
+                println!("{}", converted);
+            }
+            Address::Info { address } => {
+                let (account, ss58_prefix) = parse(&resolver, address)?;
+                let prefix = match ss58_prefix {
+                    Some(prefix) => prefix,
+                    None => self::prefix(project_dir, &None)?,
+                };
+                info(&resolver, &account, prefix)?;
+            }
+            Address::Dev { prefix } => {
+                let prefix = self::prefix(project_dir, prefix)?;
+                println!("Development accounts:");
+                for (name, _) in DEV_ACCOUNTS {
+                    if let Some(account) = dev_account(name) {
+                        println!(
+                            "    {:<8} {} {}",
+                            name,
+                            account.to_hex_literal(),
+                            address_to_ss58_with_prefix(&account, prefix)?
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Prints the address formats and names.
+fn info(resolver: &AddressResolver, account: &AccountAddress, prefix: u16) -> Result<()> {
+    println!("Hex: {}", account.to_hex_literal());
+    println!(
+        "SS58 ({}): {}",
+        prefix,
+        address_to_ss58_with_prefix(account, prefix)?
+    );
+    println!("Networks:");
+    for (network, network_ss58_prefix) in SS58_NETWORKS {
+        println!(
+            "    {} ({}): {}",
+            network,
+            network_ss58_prefix,
+            address_to_ss58_with_prefix(account, network_ss58_prefix)?
+        );
+    }
+
+    let names = resolver
+        .named_addresses()
+        .iter()
+        .filter(|(_, address)| address.as_ref() == Some(account))
+        .map(|(name, _)| name.to_string())
+        .collect::<Vec<_>>();
+    if !names.is_empty() {
+        println!("Address aliases: {}", names.join(", "));
+    }
+
+    if let Some((name, _)) = DEV_ACCOUNTS
+        .iter()
+        .find(|(name, _)| dev_account(name).as_ref() == Some(account))
+    {
+        println!("Dev account: {}", name);
+    }
+    Ok(())
+}
+
+/// Returns the address and the SS58 prefix if the address was given in the SS58 format.
+fn parse(resolver: &AddressResolver, address: &str) -> Result<(AccountAddress, Option<u16>)> {
+    let address = address.trim();
+    if !address.starts_with("0x") {
+        if let Ok((account, prefix)) = decode_ss58(address) {
+            return Ok((account, Some(prefix)));
+        }
+    }
+    Ok((resolver.resolve_account(address)?, None))
+}
+
+/// Returns the SS58 prefix: given, from Move.toml or default.
+pub(crate) fn prefix(project_dir: &Path, prefix: &Option<String>) -> Result<u16> {
+    match prefix {
+        Some(prefix) => network_prefix(prefix),
+        None => project_prefix(project_dir).map(|prefix| prefix.unwrap_or(DEFAULT_SS58_PREFIX)),
+    }
+}
+
+/// SS58 prefix of the project network:
+///     [dove]
+///     ss58_prefix = "pontem"
+fn project_prefix(project_dir: &Path) -> Result<Option<u16>> {
+    let move_toml_path = project_dir.join("Move.toml");
+    if !move_toml_path.exists() {
+        return Ok(None);
+    }
+    let move_toml = toml::from_str::<toml::Value>(&std::fs::read_to_string(&move_toml_path)?)?;
+    match move_toml
+        .get("dove")
+        .and_then(|dove| dove.get("ss58_prefix"))
+    {
+        Some(toml::Value::Integer(prefix)) => network_prefix(&prefix.to_string()).map(Some),
+        Some(toml::Value::String(network)) => network_prefix(network).map(Some),
+        Some(_) => {
+            bail!("Invalid dove.ss58_prefix in Move.toml. Expected a number or a network name")
+        }
+        None => Ok(None),
+    }
+}
+
+/// Address resolver with the project aliases, if the project exists, and the wallet keys.
+fn resolver(project_dir: &Path) -> AddressResolver {
+    let named = read_manifest(project_dir)
+        .ok()
+        .and_then(|manifest| manifest.addresses)
+        .unwrap_or_default();
+    AddressResolver::new(named).with_aliases(Box::new(wallet_key_address))
+}
@@ -4,15 +4,23 @@ use std::path::{Path, PathBuf};
 
 use structopt::StructOpt;
 use anyhow::{Error, Result};
+use url::Url;
+use move_core_types::account_address::AccountAddress;
+use net::DEFAULT_CACHE_TTL;
 
 use lang::bytecode::accessor::BytecodeRef;
 use crate::cmd::deploy::run_dove_package_build;
 use crate::context::Context;
-use crate::call::cmd::CallDeclarationCmd;
+use crate::deployments::{signer_name, Deployment, Registry};
+use crate::call::sequence::load_sequence;
+use crate::call::cmd::{CallDeclaration, CallDeclarationCmd};
+use crate::call::bytecode::build_package_name;
 use crate::call::fn_call::Config;
-use crate::call::make_transaction;
+use crate::call::make_declared_transaction;
 use crate::call::model::{EnrichedTransaction, Transaction};
-use crate::publish::{NodeAccessParams, Publish};
+use crate::call::simulate::{simulate, Simulation, State, DEFAULT_GAS_BUDGET};
+use crate::publish::{GasLimit, NodeAccessParams, Publish, PublishSequence};
+use crate::module_cache;
 
 #[derive(StructOpt, Debug)]
 #[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
@@ -20,10 +28,17 @@ use crate::publish::{NodeAccessParams, Publish};
     Examples:
     $ dove call 'script_name<0x01::Dfinance::USD>([10,10], true, ADDRESS_ALIAS, SS58_ADDRESS, 100, 0x1)'
     $ dove call 'script_name()' --args [10,10] true ADDRESS_ALIAS SS58_ADDRESS 100 0x1 --type 0x01::Dfinance::USD
+    $ dove call 'transfer(to: ADDRESS_ALIAS, amount: 100)'
     $ dove call '0x1::Module::script_name<0x01::Dfinance::USD>()'
     $ dove call 'script_name()' --account WALLET_KEY --gas 300
     $ dove call 'script_name()' --secret --url https://127.0.0.1:9933 --gas 400
     $ dove call 'script_name()' --account //Alice --gas 300
+    $ dove call 'script_name()' --account //Alice --gas auto:1.5
+    $ dove call 'script_name()' --simulate --sender //Alice
+    $ dove call 'script_name()' --simulate --sender //Alice --fork http://127.0.0.1:9933
+    $ dove call 'script_name()' --simulate --fork http://127.0.0.1:9933 --no-cache
+    $ dove call --sequence calls.toml --account //Alice --gas 300
+    $ dove call --sequence calls.toml --non-atomic --account //Alice --gas 300
 ")]
 pub struct ExecuteTransaction {
     #[structopt(flatten)]
@@ -31,38 +46,198 @@ pub struct ExecuteTransaction {
 
     #[structopt(flatten)]
     request: NodeAccessParams,
+
+    /// Execute the transaction in the local VM instead of sending it to the node.
+    /// Reports the gas used, the execution status and the write set.
+    #[structopt(long = "simulate")]
+    simulate: bool,
+
+    /// Simulate on top of the node state (JSON-RPC API url) instead of the local sandbox storage.
+    #[structopt(long = "fork")]
+    fork: Option<Url>,
+
+    /// Fetch the modules of the fork from the node
+    /// instead of the module cache (~/.move/cache/modules).
+    #[structopt(long = "no-cache", requires = "fork")]
+    no_cache: bool,
+
+    /// Transaction sender for the simulation.
+    /// Address, SS58 address, address alias or test account name. Defaults to the account.
+    #[structopt(long = "sender")]
+    sender: Option<String>,
+
+    /// Path to a TOML or JSON file with the calls to execute from one account.
+    /// The calls are sent in one Utility.batch_all extrinsic: either all of them are executed or none.
+    /// The gas limit applies to each call.
+    #[structopt(long = "sequence", parse(from_os_str), conflicts_with = "call")]
+    sequence: Option<PathBuf>,
+
+    /// Send the calls of the sequence as separate transactions, one after another.
+    /// The sequence is NOT atomic: if a call fails, the earlier calls stay committed on chain
+    /// and the rest are not sent. For runtimes without the Utility pallet.
+    #[structopt(long = "non-atomic", requires = "sequence")]
+    non_atomic: bool,
 }
 
 impl ExecuteTransaction {
     pub fn apply(&mut self, ctx: &mut Context) -> Result<()> {
         run_dove_package_build(ctx)?;
-        let tx = make_transaction(ctx, self.call.take(), Config::for_tx())?;
-        let path_transaction = match tx {
-            EnrichedTransaction::Local { .. } => unreachable!(),
-            EnrichedTransaction::Global { bi, tx, name } => {
-                store_transaction(ctx, &name, bi.bytecode_ref(), tx)?
-            }
+        let resolver = ctx.address_resolver();
+        let declarations = match &self.sequence {
+            Some(path) => load_sequence(&resolver, path)?,
+            None => vec![CallDeclaration::try_from((&resolver, self.call.take()))?],
         };
 
+        let mut txs = Vec::with_capacity(declarations.len());
+        let mut names = Vec::with_capacity(declarations.len());
+        for declaration in declarations {
+            match make_declared_transaction(ctx, declaration, Config::for_tx())? {
+                EnrichedTransaction::Local { .. } => unreachable!(),
+                EnrichedTransaction::Global { bi, tx, name } => {
+                    names.push((bi, name));
+                    txs.push(tx);
+                }
+            }
+        }
+
+        let auto_gas = matches!(self.request.gas_limit(), Some(GasLimit::Auto(_)));
+        if self.simulate || (auto_gas && self.request.need_to_publish()) {
+            let simulations = self.simulate(ctx, &txs)?;
+            for ((_, name), simulation) in names.iter().zip(&simulations) {
+                if self.sequence.is_some() {
+                    println!("Call {}:", name);
+                }
+                simulation.print();
+            }
+            let is_success =
+                simulations.len() == txs.len() && simulations.iter().all(|s| s.is_success());
+            if self.simulate {
+                if !is_success && self.sequence.is_some() {
+                    println!("The rest of the sequence was not executed.");
+                }
+                return Ok(());
+            }
+            ensure!(
+                is_success,
+                "Failed to estimate gas: the transaction fails in the simulation"
+            );
+            let gas_used = simulations
+                .iter()
+                .map(|s| s.gas_used)
+                .max()
+                .unwrap_or_default();
+            self.request.resolve_gas_limit(gas_used);
+        }
+
+        let mut paths = Vec::with_capacity(txs.len());
+        for (index, ((bi, name), tx)) in names.iter().zip(txs).enumerate() {
+            let name = match &self.sequence {
+                Some(sequence) => format!("{}_{}_{}", sequence_name(sequence), index + 1, name),
+                None => name.to_owned(),
+            };
+            paths.push(store_transaction(ctx, &name, bi.bytecode_ref(), tx)?);
+        }
+
         if !self.request.need_to_publish() {
             return Ok(());
         }
 
-        Publish::try_from((&self.request, path_transaction))?
-            .apply()
-            .map(|address| {
-                println!("Address: {}", address);
+        if self.sequence.is_some() {
+            let sequence = PublishSequence::try_from((&self.request, paths.clone()))?;
+            let signer = signer_name(sequence.signer_address());
+            let network = sequence.node_url().to_string();
+            // Each call is recorded as soon as it is executed: it stays on chain
+            // even if the next call of the sequence fails.
+            let record = |index: usize, hash: &str| {
+                let name = &names[index].1;
+                println!("Call {}: {}", name, hash);
+                let result = fs::read(&paths[index])
+                    .map_err(Error::from)
+                    .and_then(|bytes| {
+                        let mut deployment =
+                            Deployment::new("call", name, &network, &signer, &bytes);
+                        deployment.tx_hash = hash.to_owned();
+                        Registry::record(&ctx.project_root_dir, deployment)
+                    });
+                if let Err(err) = result {
+                    println!("Warning: failed to record the call {}: {}", name, err);
+                }
+            };
+            if self.non_atomic {
+                sequence.apply(record)?;
+            } else {
+                let hash = sequence.apply_batch().map_err(|err| {
+                    anyhow!(
+                        "{}\nUse --non-atomic to send the calls as separate transactions.",
+                        err
+                    )
+                })?;
+                println!("Batch: {}", hash);
+                (0..names.len()).for_each(|index| record(index, &hash));
+            }
+            Ok(())
+        } else {
+            let path = paths.remove(0);
+            let publish = Publish::try_from((&self.request, path.clone()))?;
+            let mut deployment = Deployment::new(
+                "call",
+                &names[0].1,
+                publish.node_url().as_str(),
+                &signer_name(publish.signer_address()),
+                &fs::read(&path)?,
+            );
+            let address = publish.apply()?;
+            println!("Address: {}", address);
+            deployment.tx_hash = address;
+            Registry::record(&ctx.project_root_dir, deployment)
+        }
+    }
+
+    fn simulate(&self, ctx: &Context, txs: &[Transaction]) -> Result<Vec<Simulation>> {
+        let state = match &self.fork {
+            Some(url) if self.no_cache => State::Fork(url.clone(), None),
+            Some(url) => State::Fork(url.clone(), Some(module_cache(DEFAULT_CACHE_TTL)?)),
+            None => State::Sandbox,
+        };
+        let gas_budget = match self.request.gas_limit() {
+            Some(GasLimit::Fixed(gas_limit)) => gas_limit,
+            Some(GasLimit::Auto(_)) | None => DEFAULT_GAS_BUDGET,
+        };
+        simulate(ctx, txs, &state, self.sender(ctx)?, gas_budget)
+    }
+
+    /// Returns the address of the transaction sender.
+    fn sender(&self, ctx: &Context) -> Result<AccountAddress> {
+        let sender = self
+            .sender
+            .as_deref()
+            .or_else(|| self.request.account())
+            .ok_or_else(|| anyhow!("Please specify the transaction sender: --sender"))?;
+        ctx.address_resolver()
+            .resolve_account(sender)
+            .map_err(|err| {
+                anyhow!(
+                    "Failed to resolve the sender address: {}. Please specify it with --sender",
+                    err
+                )
             })
     }
 }
 
-fn store_transaction(
+/// Returns the sequence file name without extension.
+fn sequence_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "sequence".to_string())
+}
+
+pub(crate) fn store_transaction(
     ctx: &Context,
     name: &str,
     rf: &BytecodeRef,
     tx: Transaction,
 ) -> Result<PathBuf, Error> {
-    let tx_dir = ctx.tx_output_path(get_package_from_path(&rf.0));
+    let tx_dir = ctx.tx_output_path(build_package_name(rf));
     if !tx_dir.exists() {
         fs::create_dir_all(&tx_dir)?;
     }
@@ -80,11 +255,3 @@ fn store_transaction(
 
     Ok(tx_file)
 }
-
-fn get_package_from_path<A: AsRef<Path>>(path: A) -> Option<String> {
-    let path: &Path = path.as_ref();
-    path.parent()
-        .and_then(|p| p.parent())
-        .and_then(|p| p.file_name())
-        .map(|name| name.to_string_lossy().to_string())
-}
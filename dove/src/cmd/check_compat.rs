@@ -0,0 +1,236 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use structopt::StructOpt;
+use url::Url;
+
+use move_binary_format::access::ModuleAccess;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
+use move_package::compilation::package_layout::CompiledPackageLayout;
+use net::{make_net, Net};
+
+use crate::bundle::module_id_to_string;
+use crate::cmd::deploy::{get_package_modules_path, read_modules, run_dove_package_build};
+use crate::compat::{compare_modules, deserialize, print_modules};
+use crate::context::Context;
+use crate::deployments::Registry;
+
+/// Check module upgrade compatibility
+#[derive(StructOpt, Debug)]
+#[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
+#[structopt(usage = "dove check-compat [BASELINE] [OPTIONS]
+    Examples:
+    $ dove check-compat PATH/TO/PREVIOUS/BUILD
+    $ dove check-compat PATH/TO/BUNDLE.pac
+    $ dove check-compat PATH/TO/MODULE.mv
+    $ dove check-compat --api http://127.0.0.1:9933
+    $ dove check-compat --api http://127.0.0.1:9933 --address //Alice
+")]
+pub struct CheckCompat {
+    #[structopt(
+        help = "Previous version of the modules: a build directory, a bundle (.pac) or a module (.mv)",
+        required_unless = "api"
+    )]
+    baseline: Option<PathBuf>,
+
+    #[structopt(
+        help = "The url of the substrate node to compare the modules with the on-chain code. HTTP or HTTPS only.",
+        long = "api",
+        conflicts_with = "baseline"
+    )]
+    api: Option<Url>,
+
+    #[structopt(
+        help = "The account the modules are deployed to. Defaults to the module addresses. \
+            The on-chain modules are compared as if they were published at the module addresses. \
+            Address, address alias, wallet key or dev account name.",
+        long = "address",
+        requires = "api"
+    )]
+    address: Option<String>,
+}
+
+impl CheckCompat {
+    pub fn apply(&mut self, ctx: &mut Context) -> Result<()> {
+        run_dove_package_build(ctx)?;
+
+        let modules =
+            get_package_modules_path(&ctx.project_root_dir, &ctx.manifest.package.name)?
+                .iter()
+                .map(fs::read)
+                .collect::<Result<Vec<_>, _>>()?;
+        ensure!(!modules.is_empty(), "The project has no modules");
+
+        let (plan, removed) = match (&self.baseline, &self.api) {
+            (Some(baseline), _) => {
+                let baseline = read_baseline(baseline, ctx.manifest.package.name.as_str())?;
+                let plan = compare_modules(&modules, |id| Ok(baseline.get(id).cloned()))?;
+                let removed = baseline
+                    .keys()
+                    .filter(|id| !plan.iter().any(|module| &module.id == *id))
+                    .map(module_id_to_string)
+                    .collect::<Vec<_>>();
+                (plan, removed)
+            }
+            (None, Some(api)) => {
+                let net = make_net(api.clone())?;
+                let address = self
+                    .address
+                    .as_ref()
+                    .map(|address| ctx.address_resolver().resolve_account(address))
+                    .transpose()?;
+                // Id of the module on chain.
+                let deployed_id = |id: &ModuleId| match address {
+                    Some(address) => ModuleId::new(address, id.name().to_owned()),
+                    None => id.clone(),
+                };
+                let plan = compare_modules(&modules, |id| {
+                    let bytecode = net
+                        .get_module(&deployed_id(id), &None)?
+                        .map(|bytecode| bytecode.0);
+                    match (bytecode, address) {
+                        (Some(bytecode), Some(address)) if address != *id.address() => {
+                            remap_address(&bytecode, address, *id.address()).map(Some)
+                        }
+                        (bytecode, _) => Ok(bytecode),
+                    }
+                })?;
+                let deployed = plan
+                    .iter()
+                    .map(|module| deployed_id(&module.id))
+                    .collect::<BTreeSet<_>>();
+                let removed = removed_on_chain(&ctx.project_root_dir, &*net, &deployed)?;
+                (plan, removed)
+            }
+            (None, None) => bail!("Specify the baseline or --api"),
+        };
+
+        println!("Compatibility:");
+        print_modules(&plan);
+        for id in &removed {
+            println!("    {:<12} {}", "removed", id);
+        }
+
+        let incompatible = plan
+            .iter()
+            .filter(|module| module.is_incompatible())
+            .map(|module| module_id_to_string(&module.id))
+            .chain(removed)
+            .collect::<Vec<_>>();
+        if !incompatible.is_empty() {
+            bail!(
+                "Incompatible changes in modules: {}",
+                incompatible.join(", ")
+            );
+        }
+        println!("All changes are compatible");
+        Ok(())
+    }
+}
+
+/// Replaces the address of the deployed module with the address of the local build.
+/// All references go through the address pool: the module itself, the struct handles
+/// and the type arguments of the same account are remapped together.
+fn remap_address(bytecode: &[u8], from: AccountAddress, to: AccountAddress) -> Result<Vec<u8>> {
+    let mut module = deserialize(bytecode)?;
+    for address in module
+        .address_identifiers
+        .iter_mut()
+        .filter(|address| **address == from)
+    {
+        *address = to;
+    }
+    let mut bytecode = Vec::new();
+    module.serialize(&mut bytecode)?;
+    Ok(bytecode)
+}
+
+/// Modules from the deployment registry that are still on chain, but are not in the build.
+/// The node can't list the modules of an account, so only the modules deployed
+/// from the project to the accounts of the build are checked.
+fn removed_on_chain(
+    project_dir: &Path,
+    net: &dyn Net,
+    deployed: &BTreeSet<ModuleId>,
+) -> Result<Vec<String>> {
+    let addresses = deployed
+        .iter()
+        .map(|id| *id.address())
+        .collect::<BTreeSet<_>>();
+    let candidates = Registry::load(project_dir)?
+        .deployments
+        .iter()
+        .flat_map(|deployment| &deployment.modules)
+        .filter_map(|module| parse_module_id(&module.id))
+        .filter(|id| addresses.contains(id.address()) && !deployed.contains(id))
+        .collect::<BTreeSet<_>>();
+
+    let mut removed = Vec::new();
+    for id in candidates {
+        if net.get_module(&id, &None)?.is_some() {
+            removed.push(module_id_to_string(&id));
+        }
+    }
+    Ok(removed)
+}
+
+/// Parses the module id: 0x1::Module
+fn parse_module_id(id: &str) -> Option<ModuleId> {
+    let (address, name) = id.split_once("::")?;
+    Some(ModuleId::new(
+        AccountAddress::from_hex_literal(address).ok()?,
+        Identifier::new(name).ok()?,
+    ))
+}
+
+/// Reads the modules of the build directory, the bundle or the module file.
+fn read_baseline(path: &Path, package_name: &str) -> Result<BTreeMap<ModuleId, Vec<u8>>> {
+    ensure!(path.exists(), "Baseline {:?} not found", path);
+    let modules = if path.is_dir() {
+        let modules_dir = baseline_modules_dir(path, package_name)?;
+        fs::read_dir(&modules_dir)?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|path| {
+                path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some("mv")
+            })
+            .map(fs::read)
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        read_modules(path)?
+    };
+    modules
+        .into_iter()
+        .map(|bytecode| Ok((deserialize(&bytecode)?.self_id(), bytecode)))
+        .collect()
+}
+
+/// Modules directory of the package in the baseline build. Only its own modules are compared:
+/// the dependencies and the scripts in the nested directories are skipped.
+///     BASELINE/bytecode_modules
+///     BASELINE/PACKAGE_NAME/bytecode_modules
+///     BASELINE/build/PACKAGE_NAME/bytecode_modules
+fn baseline_modules_dir(path: &Path, package_name: &str) -> Result<PathBuf> {
+    let modules = CompiledPackageLayout::CompiledModules.path();
+    [
+        path.join(modules),
+        path.join(package_name).join(modules),
+        path.join(CompiledPackageLayout::Root.path())
+            .join(package_name)
+            .join(modules),
+    ]
+    .into_iter()
+    .find(|dir| dir.is_dir())
+    .ok_or_else(|| {
+        anyhow!(
+            "Baseline {:?} is not a build directory: {:?} not found",
+            path,
+            path.join(modules)
+        )
+    })
+}
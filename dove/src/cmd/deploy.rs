@@ -1,26 +1,39 @@
-use core::mem;
-use std::collections::HashMap;
 use std::fs;
+use std::collections::{BTreeMap, BTreeSet};
 use std::str::FromStr;
 use std::fs::remove_file;
 use std::path::{PathBuf, Path};
 
-use anyhow::Error;
 use structopt::StructOpt;
-use serde::{Serialize, Deserialize};
-use anyhow::Result;
+use anyhow::{Error, Result};
 use itertools::Itertools;
+use diem_crypto::HashValue;
+use url::Url;
 
-use move_binary_format::access::ModuleAccess;
-use move_binary_format::CompiledModule;
 use move_cli::Command as MoveCommand;
 use move_cli::package::cli::PackageCommand;
 use move_cli::run_cli;
+use move_binary_format::access::ModuleAccess;
 use move_core_types::language_storage::ModuleId;
-
+use net::make_net;
+
+use crate::bundle::{
+    matches_pattern, module_id_to_string, select_modules, Bundle, ModuleHash, ModulePackage,
+};
+use crate::call::cmd::CallDeclaration;
+use crate::call::fn_call::Config;
+use crate::call::make_declared_transaction;
+use crate::call::model::EnrichedTransaction;
+use crate::call::parser::parse_call;
+use crate::cmd::call::store_transaction;
+use crate::compat::{compare_with_chain, deserialize, print_plan, ModulePlan};
 use crate::context::Context;
+use crate::deployments::{signer_name, Deployment, Registry};
 use crate::publish::{NodeAccessParams, Publish};
 
+/// Directory of the dependency packages in the build directory of the package.
+const DEPENDENCIES_DIR: &str = "dependencies";
+
 #[derive(StructOpt, Debug)]
 #[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
 #[structopt(usage = "dove deploy [FILE_NAME|PATH] [OPTIONS]
@@ -30,22 +43,90 @@ use crate::publish::{NodeAccessParams, Publish};
     $ dove deploy PACKAGE_NAME --secret --url ws://127.0.0.1:9944 --gas 400 --modules_exclude MODULE_NAME_1 MODULE_NAME_2 ..
     $ dove deploy MODULE_NAME --secret --url https://127.0.0.1:9933 --gas 400
     $ dove deploy PATH/TO/FILE --account //Alice --gas 300
+    $ dove deploy --legacy
+    $ dove deploy --modules Demo 'Store*' --modules_exclude Store_tests --api http://127.0.0.1:9933
+    $ dove deploy --plan --api http://127.0.0.1:9933
+    $ dove deploy --include_deps DemoLib 'Tools*'
+    $ dove deploy PACKAGE_NAME --account //Alice --gas 300 --init
+    $ dove deploy PACKAGE_NAME --account //Alice --gas 300 --init 'Store::init(100)'
 ")]
 pub struct Deploy {
     #[structopt(help = "Module/Bundle name or path")]
     file: Option<String>,
 
+    // * Only for bundle
+    // Names of modules to include in the package process. All modules by default.
+    // The dependencies of the included modules are added automatically.
+    // The names are case-insensitive, can be specified with an extension.mv or without it
+    // and can contain wildcards: * - any characters, ? - one character.
+    // --modules NAME_1 NAME_* NAME_?
+    #[structopt(
+        help = "Names or patterns of modules to include in the package process. \
+            The dependencies of the included modules are added automatically.",
+        long = "modules"
+    )]
+    modules: Vec<String>,
+
     // * Only for bundle
     // Names of modules to exclude from the package process.
     // Modules are taken from the <PROJECT_PATH>/build/<PROJECT_NAME>/bytecode_modules directory.
-    // The names are case-insensitive and can be specified with an extension.mv or without it.
+    // The names are case-insensitive, can be specified with an extension.mv or without it
+    // and can contain wildcards.
     // --modules_exclude NAME_1 NAME_2 NAME_3
     #[structopt(
-        help = "Names of modules to exclude from the package process.",
+        help = "Names or patterns of modules to exclude from the package process.",
         long = "modules_exclude"
     )]
     modules_exclude: Vec<String>,
 
+    // The url of the node to check whether the excluded dependencies are on chain.
+    #[structopt(
+        help = "The url of the substrate node to query modules. HTTP or HTTPS only. \
+            Defaults to the HTTP RPC of --url: ws://HOST:9944 -> http://HOST:9933.",
+        long = "api"
+    )]
+    api: Option<Url>,
+
+    // * Only for bundle
+    // The legacy bundle contains only the modules, without the package metadata.
+    #[structopt(
+        help = "Create the bundle in the legacy format without the package metadata.",
+        long = "legacy"
+    )]
+    legacy: bool,
+
+    // Script function calls executed with the same account after the publication.
+    // Without calls, the calls from Move.toml are executed:
+    //     [dove.deploy]
+    //     init = ["Module::init(args...)"]
+    #[structopt(
+        help = "Script function calls to execute after the publication with the same account. \
+            Without calls, executes dove.deploy.init from Move.toml.",
+        long = "init",
+        min_values = 0
+    )]
+    init: Option<Vec<String>>,
+
+    // * Only for bundle
+    // Names of the dependency packages to include in the bundle. Only the modules published
+    // by the deployer are included: the --account address or the addresses of the package modules.
+    // The modules are ordered across packages so that each module follows its dependencies.
+    // --include_deps PACKAGE_1 PACKAGE_2
+    #[structopt(
+        help = "Names or patterns of the dependency packages to include in the bundle. \
+            Only the modules of the deployer address are included.",
+        long = "include_deps"
+    )]
+    include_deps: Vec<String>,
+
+    // Compares the modules with the on-chain code and prints the deploy plan without publishing.
+    #[structopt(
+        help = "Print the deploy plan: new, unchanged and changed modules compared with the on-chain code. \
+            Nothing is published.",
+        long = "plan"
+    )]
+    plan: bool,
+
     #[structopt(flatten)]
     request: NodeAccessParams,
 }
@@ -58,12 +139,122 @@ impl Deploy {
         // packaging of modules
         self.bundle_modules_into_pac(ctx)?;
 
+        if self.plan {
+            return self.show_plan(ctx);
+        }
+
         if !self.request.need_to_publish() {
             return Ok(());
         }
 
+        // The init transactions are prepared before the publication to catch errors in the calls.
+        let init = self.init_transactions(ctx)?;
+
         // Publish a bundle or module to a node
-        self.publish(ctx)
+        match self.publish(ctx)? {
+            Some(publish) => self.run_init(ctx, publish, init),
+            None => {
+                if !init.is_empty() {
+                    println!("The init calls were skipped: nothing was published");
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Script function calls from --init or, if --init has no calls, from Move.toml:
+    ///     [dove.deploy]
+    ///     init = ["Module::init(args...)"]
+    fn init_calls(&self, ctx: &Context) -> Result<Vec<String>> {
+        match &self.init {
+            None => Ok(Vec::new()),
+            Some(calls) if !calls.is_empty() => Ok(calls.to_owned()),
+            Some(_) => {
+                let calls = manifest_init_calls(&ctx.project_root_dir)?;
+                ensure!(
+                    !calls.is_empty(),
+                    "No init calls. Specify them after --init or in Move.toml:\n\
+                    [dove.deploy]\n\
+                    init = [\"Module::init(args...)\"]"
+                );
+                Ok(calls)
+            }
+        }
+    }
+
+    /// Creates and stores the transactions of the init calls.
+    fn init_transactions(&self, ctx: &Context) -> Result<Vec<(String, PathBuf)>> {
+        let resolver = ctx.address_resolver();
+        self.init_calls(ctx)?
+            .iter()
+            .enumerate()
+            .map(|(index, call)| {
+                let declaration = CallDeclaration {
+                    call: parse_call(&resolver, call)?,
+                    package: None,
+                };
+                match make_declared_transaction(ctx, declaration, Config::for_tx())? {
+                    EnrichedTransaction::Local { .. } => unreachable!(),
+                    EnrichedTransaction::Global { bi, tx, name } => {
+                        let file_name = format!("init_{}_{}", index + 1, name);
+                        let path = store_transaction(ctx, &file_name, bi.bytecode_ref(), tx)?;
+                        Ok((call.to_owned(), path))
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>>>()
+            .map_err(|err| anyhow!("Invalid init call: {}", err))
+    }
+
+    /// Executes the init transactions with the connection and the account
+    /// the bundle was published with. Stops at the first failed call.
+    fn run_init(
+        &self,
+        ctx: &Context,
+        mut publish: Publish,
+        init: Vec<(String, PathBuf)>,
+    ) -> Result<()> {
+        let calls = init
+            .iter()
+            .map(|(call, _)| call.to_owned())
+            .collect::<Vec<_>>();
+        let network = publish.node_url().to_string();
+        let signer = signer_name(publish.signer_address());
+        for (index, (call, path)) in init.into_iter().enumerate() {
+            println!("Init {}: {}", index + 1, call);
+            publish.set_file_path(path.clone());
+            let result = fs::read(&path).map_err(Error::from).and_then(|bytes| {
+                let mut deployment = Deployment::new("call", &call, &network, &signer, &bytes);
+                deployment.tx_hash = publish.apply()?;
+                Ok(deployment)
+            });
+            match result {
+                Ok(deployment) => {
+                    println!("Address: {}", deployment.tx_hash);
+                    // The call is on chain: a failed registry write does not fail the init.
+                    if let Err(err) = Registry::record(&ctx.project_root_dir, deployment) {
+                        println!(
+                            "Warning: failed to record the init call '{}': {}",
+                            call, err
+                        );
+                    }
+                }
+                Err(err) => {
+                    let mut message = format!(
+                        "The modules were published, but the init call '{}' failed: {}",
+                        call, err
+                    );
+                    if index + 1 < calls.len() {
+                        message.push_str(&format!(
+                            "\nNot executed: {}",
+                            calls[index + 1..].join(", ")
+                        ));
+                    }
+                    bail!(message);
+                }
+            }
+        }
+        Ok(())
     }
 
     fn bundle_modules_into_pac(&self, ctx: &Context) -> Result<()> {
@@ -77,25 +268,34 @@ impl Deploy {
 
         // Search for modules
         let bytecode_modules_path =
-            get_bytecode_modules_path(&ctx.project_root_dir, &ctx.manifest.package.name)
+            get_package_modules_path(&ctx.project_root_dir, &ctx.manifest.package.name)
                 .unwrap_or_default();
 
-        let mut pac = ModulePackage::default();
-
-        for module in bytecode_modules_path {
-            let module_name = module.file_name().map(|name| {
-                let name = name.to_string_lossy();
-                name[0..name.len() - ".mv".len()].to_string()
-            }).ok_or_else(|| anyhow!("Failed to package move module: '{:?}'. File with .mv extension was expected.", module))?;
-            if self.modules_exclude.contains(&module_name) {
-                continue;
-            }
-            pac.put(fs::read(&module)?);
+        let mut modules = bytecode_modules_path
+            .iter()
+            .map(fs::read)
+            .collect::<Result<Vec<_>, _>>()?;
+        let dependency_modules = self.dependency_modules(ctx, &modules)?;
+        modules.extend(dependency_modules);
+        let selection = select_modules(modules, &self.modules, &self.modules_exclude)?;
+        for (dep, module) in &selection.added {
+            println!(
+                "Including '{}': required by '{}'",
+                module_id_to_string(dep),
+                module_id_to_string(module)
+            );
         }
+        if !selection.excluded_deps.is_empty() {
+            self.check_excluded_deps(&selection.excluded_deps)?;
+        }
+        let pac = selection.package;
 
-        pac.sort()?;
-
-        fs::write(&output_file_path, pac.encode()?)?;
+        let bundle = if self.legacy {
+            pac.encode()?
+        } else {
+            Bundle::new(ctx, pac)?.encode()?
+        };
+        fs::write(&output_file_path, bundle)?;
 
         println!(
             "Modules are packed {}",
@@ -107,91 +307,356 @@ impl Deploy {
         Ok(())
     }
 
+    /// Modules of the dependency packages from --include_deps published by the deployer.
+    /// The deployer is the --account address or, if it is not specified, the addresses of the package modules.
+    fn dependency_modules(&self, ctx: &Context, modules: &[Vec<u8>]) -> Result<Vec<Vec<u8>>> {
+        if self.include_deps.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let deployer: BTreeSet<_> = match self.request.account() {
+            Some(account) if !self.request.secret_phrase() => {
+                std::iter::once(ctx.address_resolver().resolve_account(account)?).collect()
+            }
+            _ => modules
+                .iter()
+                .map(|bytecode| Ok(deserialize(bytecode)?.self_id().address().to_owned()))
+                .collect::<Result<BTreeSet<_>>>()?,
+        };
+
+        let packages = get_dependency_packages(ctx)?;
+        for pattern in &self.include_deps {
+            ensure!(
+                packages.keys().any(|name| matches_pattern(pattern, name)),
+                "Dependency package '{}' not found. Compiled dependencies: {}",
+                pattern,
+                packages.keys().join(", ")
+            );
+        }
+
+        let mut dependency_modules = Vec::new();
+        for (name, paths) in packages.iter().filter(|(name, _)| {
+            self.include_deps
+                .iter()
+                .any(|pattern| matches_pattern(pattern, name))
+        }) {
+            let mut count = 0;
+            for path in paths {
+                let bytecode = fs::read(path)?;
+                let id = deserialize(&bytecode)?.self_id();
+                if deployer.contains(id.address()) {
+                    dependency_modules.push(bytecode);
+                    count += 1;
+                } else {
+                    println!(
+                        "Skipping '{}' of package '{}': the module is not published by the deployer",
+                        module_id_to_string(&id),
+                        name
+                    );
+                }
+            }
+            println!("Including package '{}': {} modules", name, count);
+        }
+        Ok(dependency_modules)
+    }
+
+    /// Checks that the excluded modules required by the bundle are already on chain.
+    /// Missing modules fail the publication and are reported as a warning otherwise.
+    fn check_excluded_deps(&self, excluded_deps: &[(ModuleId, ModuleId)]) -> Result<()> {
+        let net = self.api().and_then(make_net);
+
+        let mut missing = Vec::new();
+        for (dep, module) in excluded_deps {
+            let on_chain = match &net {
+                Ok(net) => net
+                    .get_module(dep, &None)
+                    .map(|bytecode| bytecode.is_some())
+                    .map_err(|err| format!("failed to check the chain: {}", err)),
+                Err(err) => Err(format!("the chain was not checked: {}", err)),
+            };
+            match on_chain {
+                Ok(true) => println!(
+                    "Excluded module '{}' required by '{}' is already on chain",
+                    module_id_to_string(dep),
+                    module_id_to_string(module)
+                ),
+                Ok(false) => missing.push(format!(
+                    "    {} required by {}: not found on chain",
+                    module_id_to_string(dep),
+                    module_id_to_string(module)
+                )),
+                Err(reason) => missing.push(format!(
+                    "    {} required by {}: {}",
+                    module_id_to_string(dep),
+                    module_id_to_string(module),
+                    reason
+                )),
+            }
+        }
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+        let message = format!(
+            "Excluded modules are required by the bundle:\n{}",
+            missing.join("\n")
+        );
+        if self.request.need_to_publish() {
+            bail!(
+                "{}\nInclude them in the bundle or publish them first",
+                message
+            );
+        }
+        println!("Warning: {}", message);
+        Ok(())
+    }
+
+    /// The url of the node to query modules.
+    /// Defaults to the HTTP RPC of --url.
+    fn api(&self) -> Result<Url> {
+        match &self.api {
+            Some(api) => Ok(api.clone()),
+            None => http_api(self.request.url()),
+        }
+    }
+
+    /// Compares the modules of the file with the on-chain code.
+    fn compare_with_node(&self, file_path: &Path) -> Result<Vec<ModulePlan>> {
+        let api = self.api()?;
+        compare_with_chain(&*make_net(api.clone())?, &read_modules(file_path)?).map_err(|err| {
+            anyhow!(
+                "Failed to compare the modules with the on-chain code of {}: {}\n\
+                Specify --api to query another node",
+                api,
+                err
+            )
+        })
+    }
+
+    /// Prints the deploy plan of the file or, if the file is not specified, of the project bundle.
+    fn show_plan(&self, ctx: &Context) -> Result<()> {
+        let file_path = match &self.file {
+            Some(file_name) => find_file(ctx, file_name)?,
+            None => ctx
+                .bundles_output_path(ctx.manifest.package.name.as_str())?
+                .with_extension("pac"),
+        };
+        print_plan(&self.compare_with_node(&file_path)?);
+        Ok(())
+    }
+
     /// Publish a bundle or module to a node
-    fn publish(&self, ctx: &Context) -> Result<()> {
+    /// Returns the connection the file was published with or None if nothing was published.
+    fn publish(&self, ctx: &Context) -> Result<Option<Publish>> {
         let file_name = self
             .file
             .as_ref()
             .ok_or(anyhow!("File name not specified"))?;
 
-        let file_path = if let Some(path) = str_to_path(file_name) {
-            path
-        } else {
-            search_by_file_name(&ctx.project_root_dir, file_name)?
+        let source_path = find_file(ctx, file_name)?;
+        let file_path = match self.skip_unchanged(ctx, source_path.clone())? {
+            Some(file_path) => file_path,
+            None => {
+                println!("Nothing to publish: all modules are already on chain");
+                return Ok(None);
+            }
         };
 
-        Publish::try_from((&self.request, file_path))?
-            .apply()
-            .map(|address| {
-                println!("Address: {}", address);
-            })
+        let result = self.publish_file(ctx, &source_path, &file_path);
+        if file_path != source_path {
+            fs::remove_file(&file_path).ok();
+        }
+        result.map(Some)
     }
-}
 
-#[derive(Serialize, Deserialize, Debug, Default)]
-struct ModulePackage {
-    modules: Vec<Vec<u8>>,
-}
+    /// Publishes the file and records the deployment.
+    /// `source_path` is the file the deployment is recorded for.
+    fn publish_file(
+        &self,
+        ctx: &Context,
+        source_path: &Path,
+        file_path: &Path,
+    ) -> Result<Publish> {
+        let publish = Publish::try_from((&self.request, file_path.to_owned()))?;
+        let mut deployment = new_deployment(
+            publish.node_url().as_str(),
+            &signer_name(publish.signer_address()),
+            source_path,
+            file_path,
+        )?;
+        let source_hash = match deployment.source_hash.as_str() {
+            "" => deployment.hash.to_owned(),
+            source_hash => source_hash.to_owned(),
+        };
+        let registry = Registry::load(&ctx.project_root_dir)?;
+        if let Some((number, previous)) = registry.find(&deployment.network, &source_hash) {
+            println!(
+                "Warning: the same {} was already deployed to {} at {} (deployment {})",
+                previous.kind, previous.network, previous.timestamp, number
+            );
+        }
 
-impl ModulePackage {
-    pub fn put(&mut self, module: Vec<u8>) {
-        self.modules.push(module);
+        let address = publish.apply()?;
+        println!("Address: {}", address);
+        deployment.tx_hash = address;
+        Registry::record(&ctx.project_root_dir, deployment)?;
+        Ok(publish)
     }
 
-    pub fn sort(&mut self) -> Result<(), Error> {
-        let mut modules = Vec::with_capacity(self.modules.len());
-        mem::swap(&mut self.modules, &mut modules);
-
-        let mut modules = modules
-            .into_iter()
-            .map(|bytecode| {
-                CompiledModule::deserialize(&bytecode)
-                    .map(|unit| (unit.self_id(), (bytecode, unit)))
-                    .map_err(|_| anyhow!("Failed to deserialize move module."))
-            })
-            .collect::<Result<HashMap<_, _>, _>>()?;
-
-        let mut ids_list: Vec<_> = modules.keys().cloned().collect();
-        ids_list.sort();
+    /// Compares the modules of the bundle or the module with the on-chain code.
+    /// Fails if the changed modules are incompatible with the deployed ones.
+    /// Returns the file without the unchanged modules or None if all modules are unchanged.
+    /// The file is written to the bundles directory of the project: {name}.changed.pac
+    fn skip_unchanged(&self, ctx: &Context, file_path: PathBuf) -> Result<Option<PathBuf>> {
+        if !matches!(
+            file_path.extension().and_then(|ext| ext.to_str()),
+            Some("pac") | Some("mv")
+        ) {
+            return Ok(Some(file_path));
+        }
+        let plan = self.compare_with_node(&file_path)?;
+        print_plan(&plan);
+        let incompatible = plan
+            .iter()
+            .filter(|module| module.is_incompatible())
+            .map(|module| module_id_to_string(&module.id))
+            .collect::<Vec<_>>();
+        if !incompatible.is_empty() {
+            bail!(
+                "Modules are incompatible with the on-chain code: {}",
+                incompatible.join(", ")
+            );
+        }
 
-        for id in ids_list {
-            self.write_sub_tree(&id, &mut modules);
+        if plan.iter().all(ModulePlan::need_to_publish) {
+            return Ok(Some(file_path));
         }
+        let mut package = ModulePackage::default();
+        for module in plan.into_iter().filter(ModulePlan::need_to_publish) {
+            package.put(module.bytecode);
+        }
+        if package.modules().is_empty() {
+            return Ok(None);
+        }
+        let name = file_path
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let path = ctx
+            .bundles_output_path(ctx.manifest.package.name.as_str())?
+            .with_file_name(format!("{}.changed.pac", name));
+        fs::write(&path, package.encode()?)?;
+        Ok(Some(path))
+    }
+}
 
-        Ok(())
+/// Default WebSocket and HTTP RPC ports of the substrate node.
+const DEFAULT_WS_PORT: u16 = 9944;
+const DEFAULT_HTTP_PORT: u16 = 9933;
+
+/// Returns the HTTP RPC url of the node by its url.
+///     ws://127.0.0.1:9944 => http://127.0.0.1:9933
+///     wss://rpc.pontem.network => https://rpc.pontem.network
+fn http_api(url: &Url) -> Result<Url> {
+    let scheme = match url.scheme() {
+        "http" | "https" => return Ok(url.clone()),
+        "ws" => "http",
+        "wss" => "https",
+        scheme => bail!("Unsupported node url scheme '{}'. Specify --api", scheme),
+    };
+    let mut api = url.clone();
+    api.set_scheme(scheme)
+        .map_err(|_| anyhow!("Failed to get the HTTP RPC url of {}. Specify --api", url))?;
+    if api.port() == Some(DEFAULT_WS_PORT) {
+        api.set_port(Some(DEFAULT_HTTP_PORT))
+            .map_err(|_| anyhow!("Failed to get the HTTP RPC url of {}. Specify --api", url))?;
     }
+    Ok(api)
+}
 
-    fn write_sub_tree(
-        &mut self,
-        id: &ModuleId,
-        modules: &mut HashMap<ModuleId, (Vec<u8>, CompiledModule)>,
-    ) {
-        if let Some((bytecode, unit)) = modules.remove(id) {
-            let deps = Self::take_deps(id, &unit);
-            for dep in deps {
-                self.write_sub_tree(&dep, modules);
-            }
-            println!("Packing '{}'...", id.name());
-            self.modules.push(bytecode);
-        }
+/// Returns the path to the file: the given path or the project file with this name.
+fn find_file(ctx: &Context, file_name: &str) -> Result<PathBuf> {
+    match str_to_path(file_name) {
+        Some(path) => Ok(path),
+        None => search_by_file_name(&ctx.project_root_dir, file_name),
     }
+}
 
-    fn take_deps(id: &ModuleId, unit: &CompiledModule) -> Vec<ModuleId> {
-        unit.module_handles()
+/// Init calls from Move.toml:
+///     [dove.deploy]
+///     init = ["Module::init(args...)"]
+fn manifest_init_calls(project_dir: &Path) -> Result<Vec<String>> {
+    let move_toml =
+        toml::from_str::<toml::Value>(&fs::read_to_string(project_dir.join("Move.toml"))?)?;
+    match move_toml
+        .get("dove")
+        .and_then(|dove| dove.get("deploy"))
+        .and_then(|deploy| deploy.get("init"))
+    {
+        None => Ok(Vec::new()),
+        Some(toml::Value::Array(calls)) => calls
             .iter()
-            .map(|hdl| unit.module_id_for_handle(hdl))
-            .filter(|dep_id| dep_id != id)
-            .collect()
+            .map(|call| {
+                call.as_str().map(|call| call.to_owned()).ok_or_else(|| {
+                    anyhow!("Invalid dove.deploy.init in Move.toml. Expected a list of calls")
+                })
+            })
+            .collect(),
+        Some(_) => bail!("Invalid dove.deploy.init in Move.toml. Expected a list of calls"),
     }
+}
 
-    pub fn encode(&self) -> Result<Vec<u8>, Error> {
-        bcs::to_bytes(&self).map_err(|err| err.into())
+/// Record of the file deployment. The transaction hash is set after the publication.
+/// `file_path` is the published file: the source file or, if some modules are already on chain,
+/// the bundle of the rest. The kind and the name are taken from the source file.
+fn new_deployment(
+    network: &str,
+    signer: &str,
+    source_path: &Path,
+    file_path: &Path,
+) -> Result<Deployment> {
+    let bytes = fs::read(file_path)?;
+    let kind = match source_path.extension().and_then(|ext| ext.to_str()) {
+        Some("pac") => "bundle",
+        Some("mv") => "module",
+        _ => "call",
+    };
+    let name = source_path
+        .file_stem()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut deployment = Deployment::new(kind, &name, network, signer, &bytes);
+    if source_path != file_path {
+        deployment.source_hash = HashValue::sha3_256_of(&fs::read(source_path)?).to_hex();
     }
+    if kind != "call" {
+        deployment.modules = read_modules(file_path)?
+            .iter()
+            .map(|bytecode| {
+                Ok(ModuleHash {
+                    id: module_id_to_string(&deserialize(bytecode)?.self_id()),
+                    hash: HashValue::sha3_256_of(bytecode).to_hex(),
+                })
+            })
+            .collect::<Result<_>>()?;
+    }
+    Ok(deployment)
+}
+
+/// Bytecode of the modules from the bundle or the module file.
+pub(crate) fn read_modules(file_path: &Path) -> Result<Vec<Vec<u8>>> {
+    if file_path.extension().and_then(|ext| ext.to_str()) == Some("mv") {
+        return Ok(vec![fs::read(file_path)?]);
+    }
+    Ok(Bundle::read(file_path)?.package.modules().to_vec())
 }
 
 /// Return file paths from ./PROJECT_FOLDER/build/PROJECT_NAME/bytecode_modules
 /// Only with the .mv extension
-fn get_bytecode_modules_path(project_dir: &Path, project_name: &str) -> Result<Vec<PathBuf>> {
+pub(crate) fn get_bytecode_modules_path(
+    project_dir: &Path,
+    project_name: &str,
+) -> Result<Vec<PathBuf>> {
     let path = project_dir
         .join("build")
         .join(project_name)
@@ -203,6 +668,56 @@ fn get_bytecode_modules_path(project_dir: &Path, project_name: &str) -> Result<V
     search_by_extension(&path, &["mv"])
 }
 
+/// Return file paths of the package modules without the dependencies
+/// ./PROJECT_FOLDER/build/PROJECT_NAME/bytecode_modules/*.mv
+pub(crate) fn get_package_modules_path(
+    project_dir: &Path,
+    project_name: &str,
+) -> Result<Vec<PathBuf>> {
+    let dependencies = project_dir
+        .join("build")
+        .join(project_name)
+        .join("bytecode_modules")
+        .join(DEPENDENCIES_DIR);
+    Ok(get_bytecode_modules_path(project_dir, project_name)?
+        .into_iter()
+        .filter(|path| !path.starts_with(&dependencies))
+        .collect())
+}
+
+/// Compiled dependency packages of the project and the paths to their modules.
+/// Only the packages of the resolved dependency graph of Move.toml are listed:
+///     ./PROJECT_FOLDER/build/PROJECT_NAME/bytecode_modules/dependencies/DEPENDENCY_NAME/*.mv
+pub(crate) fn get_dependency_packages(ctx: &Context) -> Result<BTreeMap<String, Vec<PathBuf>>> {
+    let graph = ctx
+        .move_args
+        .build_config
+        .clone()
+        .resolution_graph_for_package(&ctx.project_root_dir)?;
+    let dependencies = ctx
+        .project_root_dir
+        .join("build")
+        .join(ctx.manifest.package.name.as_str())
+        .join("bytecode_modules")
+        .join(DEPENDENCIES_DIR);
+
+    let mut packages = BTreeMap::new();
+    for name in graph
+        .package_table
+        .keys()
+        .filter(|name| **name != ctx.manifest.package.name)
+    {
+        let path = dependencies.join(name.as_str());
+        let modules = if path.is_dir() {
+            search_by_extension(&path, &["mv"])?
+        } else {
+            Vec::new()
+        };
+        packages.insert(name.to_string(), modules);
+    }
+    Ok(packages)
+}
+
 pub fn run_dove_package_build(ctx: &mut Context) -> Result<()> {
     let build_cmd = MoveCommand::Package {
         cmd: PackageCommand::Build {},
@@ -224,7 +739,7 @@ fn str_to_path(path: &str) -> Option<PathBuf> {
 }
 
 /// Recursive file search by extension list
-fn search_by_extension(path: &Path, list_extension: &[&str]) -> Result<Vec<PathBuf>> {
+pub(crate) fn search_by_extension(path: &Path, list_extension: &[&str]) -> Result<Vec<PathBuf>> {
     let list = fs::read_dir(path)?
         .map(|res| res.map(|e| e.path()))
         .collect::<Result<Vec<_>, _>>()?
@@ -272,3 +787,79 @@ fn search_by_file_name(path_project: &Path, file_name: &str) -> Result<PathBuf>
     }
     Ok(list.remove(0))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use diem_crypto::HashValue;
+    use move_binary_format::file_format::empty_module;
+    use move_core_types::identifier::Identifier;
+
+    use url::Url;
+
+    use crate::bundle::ModulePackage;
+    use super::{http_api, new_deployment};
+
+    fn module(name: &str) -> Vec<u8> {
+        let mut module = empty_module();
+        module.identifiers[0] = Identifier::new(name).unwrap();
+        let mut bytecode = Vec::new();
+        module.serialize(&mut bytecode).unwrap();
+        bytecode
+    }
+
+    #[test]
+    fn test_new_deployment() {
+        let dir =
+            std::env::temp_dir().join(format!("dove_deployment_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source_path = dir.join("demo.pac");
+        let changed_path = dir.join("demo.changed.pac");
+
+        let mut package = ModulePackage::default();
+        package.put(module("Store"));
+        package.put(module("StoreUser"));
+        let source = package.encode().unwrap();
+        fs::write(&source_path, &source).unwrap();
+        let mut changed = ModulePackage::default();
+        changed.put(module("StoreUser"));
+        let changed = changed.encode().unwrap();
+        fs::write(&changed_path, &changed).unwrap();
+
+        let deployment =
+            new_deployment("ws://localhost:9944", "0x1", &source_path, &source_path).unwrap();
+        assert_eq!(deployment.kind, "bundle");
+        assert_eq!(deployment.name, "demo");
+        assert_eq!(deployment.hash, HashValue::sha3_256_of(&source).to_hex());
+        assert!(deployment.source_hash.is_empty());
+        assert_eq!(deployment.modules.len(), 2);
+
+        // Only the modules that are not on chain are published.
+        let deployment =
+            new_deployment("ws://localhost:9944", "0x1", &source_path, &changed_path).unwrap();
+        assert_eq!(deployment.name, "demo");
+        assert_eq!(deployment.hash, HashValue::sha3_256_of(&changed).to_hex());
+        assert_eq!(
+            deployment.source_hash,
+            HashValue::sha3_256_of(&source).to_hex()
+        );
+        assert_eq!(deployment.modules.len(), 1);
+        assert!(deployment.modules[0].id.ends_with("::StoreUser"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_http_api() {
+        let api = |url: &str| http_api(&Url::parse(url).unwrap()).unwrap().to_string();
+        assert_eq!(api("ws://localhost:9944"), "http://localhost:9933/");
+        assert_eq!(
+            api("wss://rpc.pontem.network"),
+            "https://rpc.pontem.network/"
+        );
+        assert_eq!(api("ws://127.0.0.1:9946"), "http://127.0.0.1:9946/");
+        assert_eq!(api("http://127.0.0.1:9933"), "http://127.0.0.1:9933/");
+        assert!(http_api(&Url::parse("tcp://127.0.0.1:9944").unwrap()).is_err());
+    }
+}
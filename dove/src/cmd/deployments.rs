@@ -0,0 +1,103 @@
+use std::path::Path;
+use anyhow::Result;
+use structopt::StructOpt;
+
+use crate::deployments::{Registry, REGISTRY_FILE};
+
+/// Deployment history
+#[derive(StructOpt, Debug)]
+#[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
+#[structopt(usage = "dove deployments [SUBCOMMAND]
+    Examples:
+    $ dove deployments list
+    $ dove deployments list --network ws://127.0.0.1:9944
+    $ dove deployments show 3
+    $ dove deployments show HASH
+")]
+pub enum Deployments {
+    /// List of the deployments
+    #[structopt(name = "list")]
+    #[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
+    List {
+        /// Show only the deployments to the node with this url
+        #[structopt(long)]
+        network: Option<String>,
+    },
+
+    /// Details of the deployment
+    #[structopt(name = "show")]
+    #[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
+    Show {
+        /// Deployment number, file hash or transaction hash
+        deployment: String,
+    },
+}
+
+impl Deployments {
+    pub fn apply(&mut self, project_dir: &Path) -> Result<()> {
+        let registry = Registry::load(project_dir)?;
+        match self {
+            Deployments::List { network } => {
+                if registry.deployments.is_empty() {
+                    println!("No deployments in {}", REGISTRY_FILE);
+                    return Ok(());
+                }
+                for (index, deployment) in registry.deployments.iter().enumerate() {
+                    if network
+                        .as_ref()
+                        .map(|network| network.trim_end_matches('/'))
+                        .map(|network| deployment.network.trim_end_matches('/') != network)
+                        .unwrap_or(false)
+                    {
+                        continue;
+                    }
+                    println!(
+                        "{:<4} {} {:<7} {:<20} {} {}",
+                        index + 1,
+                        deployment.timestamp,
+                        deployment.kind,
+                        deployment.name,
+                        deployment.network,
+                        short_hash(&deployment.hash)
+                    );
+                }
+            }
+            Deployments::Show { deployment } => {
+                let deployment = deployment.trim();
+                let (number, found) = registry
+                    .deployments
+                    .iter()
+                    .enumerate()
+                    .find(|(index, found)| {
+                        deployment == (index + 1).to_string()
+                            || found.hash.eq_ignore_ascii_case(deployment)
+                            || found.tx_hash.eq_ignore_ascii_case(deployment)
+                    })
+                    .ok_or_else(|| anyhow!("Deployment '{}' not found", deployment))?;
+                println!("Deployment: {}", number + 1);
+                println!("Kind: {}", found.kind);
+                println!("Name: {}", found.name);
+                println!("Network: {}", found.network);
+                println!("Signer: {}", found.signer);
+                println!("Hash: {}", found.hash);
+                if !found.source_hash.is_empty() {
+                    println!("Source hash: {}", found.source_hash);
+                }
+                println!("Transaction: {}", found.tx_hash);
+                println!("Time: {}", found.timestamp);
+                println!("Dove: {}", found.dove_version);
+                if !found.modules.is_empty() {
+                    println!("Modules:");
+                    for module in &found.modules {
+                        println!("    {} {}", module.id, module.hash);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn short_hash(hash: &str) -> &str {
+    hash.get(..16).unwrap_or(hash)
+}
@@ -0,0 +1,193 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use diem_crypto::HashValue;
+use structopt::StructOpt;
+
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::file_format::Visibility;
+use move_binary_format::normalized::Module;
+use move_core_types::language_storage::ModuleId;
+
+use crate::bundle::{module_id_to_string, Bundle, BundleMetadata, ModulePackage};
+use crate::compat::{abilities_to_string, deserialize, signature_to_string};
+use crate::context::read_manifest;
+
+/// Bundle and module inspector
+#[derive(StructOpt, Debug)]
+#[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
+#[structopt(usage = "dove package inspect [PATH] [OPTIONS]
+    Examples:
+    $ dove package inspect
+    $ dove package inspect PATH/TO/BUNDLE.pac
+    $ dove package inspect PATH/TO/MODULE.mv
+    $ dove package inspect PATH/TO/BUNDLE.pac --dot > deps.dot
+")]
+pub struct Inspect {
+    #[structopt(
+        help = "Path to the bundle (.pac) or the module (.mv). Defaults to the project bundle."
+    )]
+    file: Option<PathBuf>,
+
+    #[structopt(help = "Print the dependency graph in the DOT format", long = "dot")]
+    dot: bool,
+}
+
+/// Module of the inspected file.
+struct ModuleInfo {
+    id: ModuleId,
+    size: usize,
+    hash: String,
+    structs: Vec<String>,
+    script_functions: Vec<String>,
+    deps: Vec<ModuleId>,
+}
+
+impl Inspect {
+    /// Works without a project if the file is specified.
+    pub fn apply(&mut self, project_dir: &Path) -> Result<()> {
+        let file_path = match &self.file {
+            Some(file_path) => file_path.to_owned(),
+            None => {
+                let name = read_manifest(project_dir)?.package.name.to_string();
+                project_dir
+                    .join("build")
+                    .join(&name)
+                    .join("bundles")
+                    .join(name)
+                    .with_extension("pac")
+            }
+        };
+        ensure!(file_path.exists(), "File {:?} not found", file_path);
+
+        let (metadata, modules) =
+            if file_path.extension().and_then(|ext| ext.to_str()) == Some("mv") {
+                (None, vec![fs::read(&file_path)?])
+            } else {
+                let bundle = Bundle::read(&file_path)?;
+                (bundle.metadata, bundle.package.modules().to_vec())
+            };
+        let modules = modules
+            .iter()
+            .map(|bytecode| module_info(bytecode))
+            .collect::<Result<Vec<_>>>()?;
+
+        if self.dot {
+            let name = metadata
+                .as_ref()
+                .map(|metadata| metadata.name.to_owned())
+                .or_else(|| {
+                    file_path
+                        .file_stem()
+                        .map(|name| name.to_string_lossy().to_string())
+                })
+                .unwrap_or_default();
+            print_dot(&name, &modules);
+        } else {
+            print_info(metadata.as_ref(), &modules);
+        }
+        Ok(())
+    }
+}
+
+fn module_info(bytecode: &[u8]) -> Result<ModuleInfo> {
+    let unit = deserialize(bytecode)?;
+    let id = unit.self_id();
+    let deps = ModulePackage::take_deps(&id, &unit);
+    let module = Module::new(&unit);
+
+    let structs = module
+        .structs
+        .iter()
+        .map(|(name, st)| {
+            let abilities = abilities_to_string(st.abilities);
+            if st.type_parameters.is_empty() {
+                format!("{} has {}", name, abilities)
+            } else {
+                let type_parameters = (0..st.type_parameters.len())
+                    .map(|index| format!("T{}", index))
+                    .collect::<Vec<_>>();
+                format!("{}<{}> has {}", name, type_parameters.join(", "), abilities)
+            }
+        })
+        .collect();
+    let script_functions = module
+        .exposed_functions
+        .iter()
+        .filter(|(_, func)| func.visibility == Visibility::Script)
+        .map(|(name, func)| format!("{}{}", name, signature_to_string(func)))
+        .collect();
+
+    Ok(ModuleInfo {
+        id,
+        size: bytecode.len(),
+        hash: HashValue::sha3_256_of(bytecode).to_hex(),
+        structs,
+        script_functions,
+        deps,
+    })
+}
+
+fn print_info(metadata: Option<&BundleMetadata>, modules: &[ModuleInfo]) {
+    if let Some(metadata) = metadata {
+        println!("Package: {} {}", metadata.name, metadata.version);
+        println!("Source digest: {}", metadata.source_digest);
+        println!(
+            "Built with: dove {}, move {}",
+            metadata.build_info.dove, metadata.build_info.move_version
+        );
+    }
+    println!("Modules: {}", modules.len());
+
+    for module in modules {
+        println!();
+        println!("{}", module_id_to_string(&module.id));
+        println!("    Size: {} bytes", module.size);
+        println!("    Hash: {}", module.hash);
+        print_list("Structs", &module.structs);
+        print_list("Script functions", &module.script_functions);
+        print_list(
+            "Dependencies",
+            &module
+                .deps
+                .iter()
+                .map(module_id_to_string)
+                .collect::<Vec<_>>(),
+        );
+    }
+}
+
+fn print_list(title: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    println!("    {}:", title);
+    for item in items {
+        println!("        {}", item);
+    }
+}
+
+/// Dependencies outside of the bundle are dashed.
+fn print_dot(name: &str, modules: &[ModuleInfo]) {
+    println!("digraph \"{}\" {{", name);
+    for module in modules {
+        println!("    \"{}\";", module_id_to_string(&module.id));
+    }
+    for module in modules {
+        for dep in &module.deps {
+            let style = if modules.iter().any(|module| &module.id == dep) {
+                ""
+            } else {
+                " [style=dashed]"
+            };
+            println!(
+                "    \"{}\" -> \"{}\"{};",
+                module_id_to_string(&module.id),
+                module_id_to_string(dep),
+                style
+            );
+        }
+    }
+    println!("}}");
+}
@@ -1,12 +1,24 @@
+/// Address tools.
+pub mod address;
 /// Create transaction.
 pub mod call;
+/// Module upgrade compatibility.
+pub mod check_compat;
 /// Project dependencies loader.
 pub mod clean;
 /// Project builder.
 pub mod deploy;
+/// Deployment history.
+pub mod deployments;
+/// Bundle and module inspector.
+pub mod inspect;
 /// Manage wallet keys
 pub mod key;
+/// Deployment pipelines.
+pub mod pipeline;
 /// Script executor.
 pub mod run;
+/// Source verification.
+pub mod verify;
 /// resource-viewer
 pub mod view;
@@ -0,0 +1,345 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Deserialize;
+use structopt::StructOpt;
+use url::Url;
+
+use move_core_types::language_storage::TypeTag;
+use move_resource_viewer::{AnnotatedMoveStruct, MoveValueAnnotator};
+use net::{make_cached_net, NetView};
+
+use crate::call::args_file::read_file;
+use crate::cmd::call::ExecuteTransaction;
+use crate::cmd::deploy::{run_dove_package_build, Deploy};
+use crate::cmd::view::{parse_query, split_account};
+use crate::context::Context;
+use crate::render::{self, field, render, AddressFormat};
+
+/// Deployment pipelines
+#[derive(StructOpt, Debug)]
+#[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
+#[structopt(usage = "dove pipeline run [FILE] --network [NAME]
+    Examples:
+    $ dove pipeline run deploy.toml --network local
+    $ dove pipeline run deploy.json --network testnet
+")]
+pub enum Pipeline {
+    /// Execute the steps of the pipeline one after another
+    #[structopt(name = "run")]
+    #[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
+    Run {
+        /// Path to a TOML or JSON file with the pipeline
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+
+        /// Network profile from the pipeline file
+        #[structopt(long)]
+        network: String,
+    },
+}
+
+/// Pipeline file.
+///
+/// TOML:
+///     [network.local]
+///     url = "ws://127.0.0.1:9944"
+///     api = "http://127.0.0.1:9933"
+///     account = "alice"
+///     gas = 300
+///     vars = { amount = 100 }
+///
+///     [[step]]
+///     build = true
+///
+///     [[step]]
+///     deploy = ""
+///
+///     [[step]]
+///     call = "Store::store_u64(${amount})"
+///
+///     [[step]]
+///     view = "${account}::Store::U64"
+///     expect = { value = "${amount}" }
+///     save = { stored = "value" }
+#[derive(Deserialize, Debug)]
+struct PipelineFile {
+    #[serde(default)]
+    network: BTreeMap<String, Network>,
+    #[serde(rename = "step")]
+    steps: Vec<Step>,
+}
+
+/// Network profile.
+#[derive(Deserialize, Debug, Default)]
+struct Network {
+    /// The url of the node to send the transactions to.
+    url: Option<Url>,
+    /// The url of the node to query. HTTP or HTTPS only.
+    api: Option<Url>,
+    /// Account from whom to publish and execute the transactions.
+    account: Option<String>,
+    /// Request the secret phrase instead of the account.
+    #[serde(default)]
+    secret: bool,
+    /// Limitation of gas consumption per transaction: 300 or "auto:1.5".
+    gas: Option<serde_json::Value>,
+    /// Variables of the network.
+    #[serde(default)]
+    vars: BTreeMap<String, serde_json::Value>,
+}
+
+/// Step of the pipeline. Exactly one of build, deploy, call or view must be set.
+#[derive(Deserialize, Debug)]
+struct Step {
+    /// Step name for the messages.
+    name: Option<String>,
+    /// Build the project.
+    #[serde(default)]
+    build: bool,
+    /// Publish the bundle or the module. An empty string is the project bundle.
+    deploy: Option<String>,
+    /// Execute the script or the script function.
+    call: Option<String>,
+    /// Query the resource: [ACCOUNT@]ADDRESS::MODULE::TYPE_NAME<GENERIC_PARAMS>
+    view: Option<String>,
+    /// Additional arguments of `dove deploy` or `dove call`.
+    #[serde(default)]
+    args: Vec<String>,
+    /// Expected values of the resource fields: { "coin.value" = "100" }
+    #[serde(default)]
+    expect: BTreeMap<String, serde_json::Value>,
+    /// Resource fields saved to the variables: { balance = "coin.value" }
+    #[serde(default)]
+    save: BTreeMap<String, String>,
+}
+
+impl Pipeline {
+    pub fn apply(&mut self, ctx: &mut Context) -> Result<()> {
+        match self {
+            Pipeline::Run { file, network } => run(ctx, file, network),
+        }
+    }
+}
+
+fn run(ctx: &mut Context, path: &Path, network_name: &str) -> Result<()> {
+    let pipeline: PipelineFile = serde_json::from_value(read_file(path)?)
+        .map_err(|err| anyhow!("Failed to parse pipeline {:?}: {}", path, err))?;
+    ensure!(
+        !pipeline.steps.is_empty(),
+        "The pipeline {:?} is empty",
+        path
+    );
+    let network = pipeline.network.get(network_name).ok_or_else(|| {
+        anyhow!(
+            "Network '{}' not found in {:?}. Networks: {}",
+            network_name,
+            path,
+            pipeline
+                .network
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })?;
+
+    let mut vars = network
+        .vars
+        .iter()
+        .map(|(name, value)| (name.to_owned(), value_to_string(value)))
+        .collect::<BTreeMap<_, _>>();
+    vars.insert("network".to_string(), network_name.to_string());
+    if let Some(account) = &network.account {
+        vars.insert("account".to_string(), account.to_owned());
+    }
+    if let Some(url) = &network.url {
+        vars.insert("url".to_string(), url.to_string());
+    }
+    if let Some(api) = &network.api {
+        vars.insert("api".to_string(), api.to_string());
+    }
+
+    for (index, step) in pipeline.steps.iter().enumerate() {
+        let name = step
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("step {}", index + 1));
+        println!("Pipeline {}:", name);
+        run_step(ctx, network, step, &mut vars)
+            .map_err(|err| anyhow!("Pipeline {} failed: {}", name, err))?;
+    }
+    println!("Pipeline completed");
+    Ok(())
+}
+
+fn run_step(
+    ctx: &mut Context,
+    network: &Network,
+    step: &Step,
+    vars: &mut BTreeMap<String, String>,
+) -> Result<()> {
+    let actions = [
+        step.build,
+        step.deploy.is_some(),
+        step.call.is_some(),
+        step.view.is_some(),
+    ];
+    ensure!(
+        actions.iter().filter(|action| **action).count() == 1,
+        "Expected exactly one of build, deploy, call or view"
+    );
+    ensure!(
+        step.view.is_some() || (step.expect.is_empty() && step.save.is_empty()),
+        "expect and save are supported only by view"
+    );
+
+    let args = step
+        .args
+        .iter()
+        .map(|arg| substitute(arg, vars))
+        .collect::<Result<Vec<_>>>()?;
+
+    if step.build {
+        run_dove_package_build(ctx)
+    } else if let Some(file) = &step.deploy {
+        let mut cmd_args = vec!["deploy".to_string()];
+        let file = substitute(file, vars)?;
+        if !file.is_empty() {
+            cmd_args.push(file);
+        }
+        cmd_args.extend(node_args(network)?);
+        cmd_args.extend(args);
+        Deploy::from_iter_safe(cmd_args)?.apply(ctx)
+    } else if let Some(call) = &step.call {
+        let mut cmd_args = vec!["call".to_string(), substitute(call, vars)?];
+        cmd_args.extend(node_args(network)?);
+        cmd_args.extend(args);
+        ExecuteTransaction::from_iter_safe(cmd_args)?.apply(ctx)
+    } else if let Some(query) = &step.view {
+        let api = network
+            .api
+            .clone()
+            .ok_or_else(|| anyhow!("Specify api of the network to view resources"))?;
+        let resource = view(ctx, api, &substitute(query, vars)?)?;
+        println!("{}", resource);
+
+        for (field, expected) in &step.expect {
+            let expected = substitute(&value_to_string(expected), vars)?;
+            let actual = field_value(&resource, field)?;
+            ensure!(
+                actual == expected,
+                "{}: expected {}, got {}",
+                field,
+                expected,
+                actual
+            );
+        }
+        for (var, field) in &step.save {
+            vars.insert(var.to_owned(), field_value(&resource, field)?);
+        }
+        Ok(())
+    } else {
+        unreachable!()
+    }
+}
+
+/// Connection parameters of `dove deploy` and `dove call`.
+fn node_args(network: &Network) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    if let Some(url) = &network.url {
+        args.extend(["--url".to_string(), url.to_string()]);
+    }
+    if network.secret {
+        args.push("--secret".to_string());
+    } else {
+        let account = network
+            .account
+            .as_ref()
+            .ok_or_else(|| anyhow!("Specify account or secret of the network"))?;
+        args.extend(["--account".to_string(), account.to_owned()]);
+    }
+    if let Some(gas) = &network.gas {
+        args.extend(["--gas".to_string(), value_to_string(gas)]);
+    }
+    Ok(args)
+}
+
+/// Queries the resource from the node.
+fn view(ctx: &Context, api: Url, query: &str) -> Result<AnnotatedMoveStruct> {
+    let resolver = ctx.address_resolver();
+    let (account, tp) = split_account(query);
+    let st = match parse_query(&resolver, tp)? {
+        TypeTag::Struct(st) => st,
+        tp => bail!("Unsupported type {}", tp),
+    };
+    let account = match account {
+        Some(account) => resolver.resolve_account(account)?,
+        None => st.address,
+    };
+    // The steps change the modules on chain, so the views bypass the module cache.
+    let net = make_cached_net(api, None)?;
+    let bytes = net
+        .get_resource(&account, &st, &None)?
+        .ok_or_else(|| anyhow!("Resource {} not found", query))?;
+    let view = NetView::new(net, None);
+    MoveValueAnnotator::new(&view).view_resource(&st, &bytes.0)
+}
+
+/// Value of the resource field as `dove view --format table` prints it: coin.value, items[0]
+fn field_value(resource: &AnnotatedMoveStruct, path: &str) -> Result<String> {
+    field(&render(resource, AddressFormat::Hex), path)
+        .map(render::value_to_string)
+        .ok_or_else(|| anyhow!("Field {} not found", path))
+}
+
+fn value_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(value) => value.to_owned(),
+        value => value.to_string(),
+    }
+}
+
+/// Replaces ${name} with the values of the variables.
+fn substitute(text: &str, vars: &BTreeMap<String, String>) -> Result<String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| anyhow!("Unclosed variable in '{}'", text))?;
+        let name = rest[start + 2..start + end].trim();
+        let value = vars
+            .get(name)
+            .ok_or_else(|| anyhow!("Unknown variable '{}' in '{}'", name, text))?;
+        result.push_str(value);
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use super::substitute;
+
+    #[test]
+    fn test_substitute() {
+        let vars = vec![
+            ("amount".to_string(), "100".to_string()),
+            ("account".to_string(), "alice".to_string()),
+        ]
+        .into_iter()
+        .collect::<BTreeMap<_, _>>();
+        assert_eq!(
+            substitute("transfer(${account}, ${ amount })", &vars).unwrap(),
+            "transfer(alice, 100)"
+        );
+        assert_eq!(substitute("main()", &vars).unwrap(), "main()");
+        assert!(substitute("${unknown}", &vars).is_err());
+        assert!(substitute("${amount", &vars).is_err());
+    }
+}
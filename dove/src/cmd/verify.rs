@@ -0,0 +1,186 @@
+use std::fs;
+
+use anyhow::Result;
+use structopt::StructOpt;
+use url::Url;
+
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::CompiledModule;
+use move_core_types::language_storage::ModuleId;
+use net::make_net;
+
+use crate::bundle::{matches_pattern, module_id_to_string};
+use crate::cmd::deploy::{get_package_modules_path, run_dove_package_build};
+use crate::compat::deserialize;
+use crate::context::Context;
+
+/// Verify the deployed modules against the project sources
+#[derive(StructOpt, Debug)]
+#[structopt(setting(structopt::clap::AppSettings::ColoredHelp))]
+#[structopt(usage = "dove verify --address ADDRESS [OPTIONS]
+    Examples:
+    $ dove verify --address 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY
+    $ dove verify --address //Alice --module Store --api http://127.0.0.1:9933
+    $ dove verify --address Demo --module 'Store*' Demo1v
+")]
+pub struct Verify {
+    #[structopt(
+        help = "The account the modules are deployed to. \
+            Address, address alias, wallet key or dev account name.",
+        long = "address"
+    )]
+    address: String,
+
+    #[structopt(
+        help = "Names or patterns of modules to verify. All project modules by default.",
+        long = "module"
+    )]
+    modules: Vec<String>,
+
+    #[structopt(
+        help = "The url of the substrate node to query. HTTP or HTTPS only",
+        long = "api",
+        default_value = "http://127.0.0.1:9933"
+    )]
+    api: Url,
+}
+
+/// Result of the module verification.
+#[derive(Debug, PartialEq)]
+enum Verification {
+    /// The bytecode is the same.
+    Match,
+    /// The bytecode is the same after the deserialization and serialization.
+    /// The binary encoding differs.
+    NormalizedMatch,
+    /// The bytecode differs.
+    Mismatch,
+    /// The module is not on chain.
+    Missing,
+}
+
+impl Verify {
+    pub fn apply(&mut self, ctx: &mut Context) -> Result<()> {
+        let address = ctx.address_resolver().resolve_account(&self.address)?;
+
+        // Build the project with the settings from Move.toml.
+        run_dove_package_build(ctx)?;
+
+        let modules =
+            get_package_modules_path(&ctx.project_root_dir, &ctx.manifest.package.name)?
+                .iter()
+                .map(fs::read)
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .map(|bytecode| Ok((deserialize(&bytecode)?, bytecode)))
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .filter(|(unit, _)| {
+                    self.modules.is_empty()
+                        || self.modules.iter().any(|pattern| {
+                            matches_pattern(pattern, unit.self_id().name().as_str())
+                        })
+                })
+                .collect::<Vec<_>>();
+        ensure!(!modules.is_empty(), "No modules to verify");
+
+        let net = make_net(self.api.clone())?;
+        let mut failed = Vec::new();
+        println!("Verifying modules of {}:", address.to_hex_literal());
+        for (unit, bytecode) in &modules {
+            let local_id = unit.self_id();
+            let id = ModuleId::new(address, local_id.name().to_owned());
+            let verification = match net.get_module(&id, &None)? {
+                None => Verification::Missing,
+                Some(deployed) => compare(unit, bytecode, &deployed.0)?,
+            };
+            let status = match verification {
+                Verification::Match => "match",
+                Verification::NormalizedMatch => "match (normalized encoding)",
+                Verification::Mismatch => "mismatch",
+                Verification::Missing => "not found on chain",
+            };
+            println!("    {}: {}", module_id_to_string(&id), status);
+            if matches!(verification, Verification::Mismatch | Verification::Missing) {
+                if local_id.address() != id.address() {
+                    println!(
+                        "        the module is built for {}. Check the address in Move.toml",
+                        local_id.address().to_hex_literal()
+                    );
+                }
+                failed.push(module_id_to_string(&id));
+            }
+        }
+
+        ensure!(
+            failed.is_empty(),
+            "The deployed modules do not match the sources: {}",
+            failed.join(", ")
+        );
+        println!("All modules match the sources");
+        Ok(())
+    }
+}
+
+/// Compares the bytecode byte-for-byte.
+/// If the bytes differ, compares the modules re-serialized in the canonical encoding.
+fn compare(unit: &CompiledModule, local: &[u8], deployed: &[u8]) -> Result<Verification> {
+    if local == deployed {
+        return Ok(Verification::Match);
+    }
+    let deployed = match CompiledModule::deserialize(deployed) {
+        Ok(deployed) => deployed,
+        Err(_) => return Ok(Verification::Mismatch),
+    };
+    if serialize(unit)? == serialize(&deployed)? {
+        Ok(Verification::NormalizedMatch)
+    } else {
+        Ok(Verification::Mismatch)
+    }
+}
+
+fn serialize(unit: &CompiledModule) -> Result<Vec<u8>> {
+    let mut bytecode = Vec::new();
+    unit.serialize(&mut bytecode)?;
+    Ok(bytecode)
+}
+
+#[cfg(test)]
+mod tests {
+    use move_binary_format::file_format::empty_module;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+    use super::{compare, serialize, Verification};
+
+    #[test]
+    fn test_compare() {
+        let mut unit = empty_module();
+        unit.identifiers[0] = Identifier::new("Demo").unwrap();
+        let bytecode = serialize(&unit).unwrap();
+        assert_eq!(
+            compare(&unit, &bytecode, &bytecode).unwrap(),
+            Verification::Match
+        );
+
+        // The local encoding differs, the module is the same.
+        let mut local = bytecode.clone();
+        local.push(0);
+        assert_eq!(
+            compare(&unit, &local, &bytecode).unwrap(),
+            Verification::NormalizedMatch
+        );
+
+        let mut other = unit.clone();
+        other.address_identifiers[0] = AccountAddress::new([7; AccountAddress::LENGTH]);
+        let other = serialize(&other).unwrap();
+        assert_ne!(other, bytecode);
+        assert_eq!(
+            compare(&unit, &bytecode, &other).unwrap(),
+            Verification::Mismatch
+        );
+        assert_eq!(
+            compare(&unit, &bytecode, &[1, 2, 3]).unwrap(),
+            Verification::Mismatch
+        );
+    }
+}
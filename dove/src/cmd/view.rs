@@ -1,17 +1,28 @@
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use anyhow::Error;
 use structopt::StructOpt;
 use log::{error, info};
+use serde::Serialize;
 use reqwest::Url;
 
-use move_core_types::language_storage::TypeTag;
-use move_package::source_package::parsed_manifest::{AddressDeclarations, NamedAddress};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use move_core_types::resolver::ResourceResolver;
+use move_resource_viewer::{AnnotatedMoveStruct, MoveValueAnnotator};
 
-use lang::ss58::ss58_to_address;
+use lang::address::AddressResolver;
 use resource_viewer::ser;
-use net::{make_net, NetView};
+use net::{make_cached_net, Net, NetView};
 
+use crate::cmd::address::prefix;
 use crate::context::Context;
+use crate::module_cache;
+use crate::render::{
+    flatten, render, summary, to_csv, to_table, to_yaml, AddressFormat, OutputFormat,
+};
+use crate::resource_diff::{diff, Change};
+use crate::sandbox::SandboxNet;
 use crate::call::parser::parse_type_param;
 
 /// Move Resource Viewer
@@ -21,18 +32,32 @@ use crate::call::parser::parse_type_param;
     Examples:
     $ dove view Account::Store::U64
     $ dove view Account::Store::U64 --api http://127.0.0.1:9933
+    $ dove view //Alice::Store::U64 --api http://127.0.0.1:9933
     $ dove view Account::Store::U64 --api http://127.0.0.1:9933 --json
     $ dove view 0x1::Account::Balance<0x1::Coins::ETH> --api http://127.0.0.1:9933 --json --output PATH/SAVE.json
+    $ dove view Account::Store::U64 --local
+    $ dove view 0x1::Account::Balance<0x1::PONT::PONT> --account //Alice
+    $ dove view '//Alice@0x1::Account::Balance<0x1::PONT::PONT>'
+    $ dove view --account //Alice --all --local
+    $ dove view --account Account --all --local --json
+    $ dove view Account::Store::U64 --from 100 --to 200 --step 10
+    $ dove view Account::Store::U64 --diff 100 200
+    $ dove view 0x1::Account::Balance<0x1::PONT::PONT> --format yaml --address-format ss58
+    $ dove view 0x1::Account::Balance<0x1::PONT::PONT> --format csv --output balance.csv
+    $ dove view 0x1::Account::Balance<0x1::PONT::PONT> --json --js-safe --json-schema schema.json
+    $ dove view Account::Store::U64 --no-cache
 ")]
 pub struct View {
     #[structopt(
         display_order = 1,
-        help = "Fully qualified type description in a form of ADDRESS::MODULE::TYPE_NAME<GENERIC_PARAMS> \n\
+        help = "Fully qualified type description in a form of [ACCOUNT@]ADDRESS::MODULE::TYPE_NAME<GENERIC_PARAMS> \n\
             Examples: \n\
             Account::Store::U64 \n\
-            0x1::Account::Balance<0x1::Coins::ETH>"
+            0x1::Account::Balance<0x1::Coins::ETH> \n\
+            //Alice@0x1::Account::Balance<0x1::Coins::ETH>",
+        required_unless = "all"
     )]
-    query: String,
+    query: Option<String>,
 
     #[structopt(
         long,
@@ -45,6 +70,26 @@ pub struct View {
     #[structopt(long, short, display_order = 3, help = "Sets output format to JSON")]
     json: bool,
 
+    #[structopt(
+        long,
+        display_order = 3,
+        help = "Output format: text, json, yaml, table or csv. \
+            yaml, table and csv render well-known values: U256, ASCII::String, Option, text bytes",
+        default_value = "text",
+        conflicts_with = "json"
+    )]
+    format: OutputFormat,
+
+    #[structopt(
+        long = "address-format",
+        display_order = 3,
+        help = "Address format of yaml, table and csv: hex or ss58. \
+            SS58 uses dove.ss58_prefix of Move.toml or 42",
+        default_value = "hex",
+        possible_values = &["hex", "ss58"]
+    )]
+    address_format: String,
+
     #[structopt(
         long = "json-schema",
         display_order = 4,
@@ -52,84 +97,397 @@ pub struct View {
     )]
     json_schema: Option<PathBuf>,
 
+    #[structopt(
+        long = "js-safe",
+        display_order = 4,
+        help = "JSON safe for JavaScript: addresses as hex strings, \
+            u64 and u128 as decimal strings, type tags as strings. \
+            Requires the JSON output or --json-schema. Not supported by --from and --diff"
+    )]
+    js_safe: bool,
+
     #[structopt(long, short, display_order = 5, help = "Path to output file")]
     output: Option<PathBuf>,
 
     #[structopt(long, short, display_order = 6, help = "Block number")]
     height: Option<String>,
+
+    #[structopt(
+        long,
+        display_order = 7,
+        help = "Account that holds the resources. Defaults to the address of the resource module. \
+            Address, SS58 address, address alias or test account name"
+    )]
+    account: Option<String>,
+
+    #[structopt(
+        long,
+        display_order = 8,
+        help = "List all resources stored under the account in the local sandbox. \
+            The node can't list the resources of an account",
+        requires_all = &["account", "local"],
+        conflicts_with = "query"
+    )]
+    all: bool,
+
+    #[structopt(
+        long,
+        display_order = 9,
+        help = "Query the local sandbox storage (./storage) instead of the node",
+        conflicts_with_all = &["height", "from", "diff"]
+    )]
+    local: bool,
+
+    #[structopt(
+        long,
+        display_order = 10,
+        help = "Show the changes of the resource starting from this block number",
+        requires = "to",
+        conflicts_with_all = &["height", "all", "diff"]
+    )]
+    from: Option<u64>,
+
+    #[structopt(
+        long,
+        display_order = 11,
+        help = "Show the changes of the resource up to this block number",
+        requires = "from"
+    )]
+    to: Option<u64>,
+
+    #[structopt(
+        long,
+        display_order = 12,
+        help = "Distance between the compared blocks. Used with --from",
+        default_value = "1"
+    )]
+    step: u64,
+
+    #[structopt(
+        long,
+        display_order = 13,
+        help = "Show the changes of the resource between two blocks. Block numbers or hashes",
+        number_of_values = 2,
+        value_names = &["HEIGHT_1", "HEIGHT_2"],
+        conflicts_with_all = &["height", "all"]
+    )]
+    diff: Option<Vec<String>>,
+
+    #[structopt(
+        long = "no-cache",
+        display_order = 14,
+        help = "Fetch the modules from the node instead of the module cache (~/.move/cache/modules)"
+    )]
+    no_cache: bool,
+
+    #[structopt(
+        long = "cache-ttl",
+        display_order = 15,
+        help = "Lifetime of the cached modules of the latest state in seconds. \
+            Modules of a given block never expire",
+        default_value = "600"
+    )]
+    cache_ttl: u64,
+}
+
+/// Maximum number of the snapshots of `--from --to`.
+const MAX_SNAPSHOTS: u64 = 1000;
+
+/// Resource at the block and its changes since the previous snapshot.
+#[derive(Serialize)]
+struct Snapshot {
+    height: String,
+    exists: bool,
+    changes: Vec<Change>,
 }
 
 impl View {
     pub fn apply(&mut self, ctx: &mut Context) -> anyhow::Result<()> {
+        if self.js_safe {
+            ensure!(
+                self.from.is_none() && self.diff.is_none(),
+                "--js-safe is not supported by --from and --diff"
+            );
+            ensure!(
+                self.output_format() == OutputFormat::Json || self.json_schema.is_some(),
+                "--js-safe requires --json, --format json or --json-schema"
+            );
+        }
         if let Some(path) = self.json_schema.as_ref() {
-            produce_json_schema(path);
+            produce_json_schema(path, self.js_safe);
+        }
+
+        if self.all {
+            return self.view_all(ctx);
+        }
+
+        if self.from.is_some() || self.diff.is_some() {
+            return self.view_history(ctx);
         }
 
         let height = self.height.clone();
-        let net = make_net(self.api.clone())?;
-        let address_map = ctx.manifest.addresses.clone().unwrap_or_default();
-
-        if !self.query.starts_with("0x") {
-            if let Some(pos) = self.query.find("::") {
-                let name_address = &self.query[..pos];
-                let address = address_map
-                    .get(&NamedAddress::from(name_address))
-                    .map(|acc| {
-                        acc.ok_or(anyhow!(
-                            "In Move.toml address not assigned to alias {}",
-                            name_address
-                        ))
-                    })
-                    .unwrap_or_else(|| ss58_to_address(name_address))?;
-
-                self.query = format!("{}{}", address.to_hex_literal(), &self.query[pos..]);
-            }
+        let net = self.net(ctx)?;
+        let (addr, st) = self.resource(ctx)?;
+        let format = self.output_format();
+        let addresses = self.address_format(ctx)?;
+
+        net.get_resource(&addr, &st, &height)
+            .map(|resp| {
+                let view = NetView::new(net, height);
+                if let Some(bytes_for_block) = resp {
+                    // Internally produce FatStructType (with layout) for StructTag by
+                    // resolving & de-.. entire deps-chain.
+                    let annotator = MoveValueAnnotator::new(&view);
+
+                    annotator
+                        .view_resource(&st, &bytes_for_block.0)
+                        .and_then(|result| {
+                            let height = bytes_for_block.1;
+
+                            match format {
+                                OutputFormat::Text => Ok(format!("{}", result)),
+                                OutputFormat::Json if self.js_safe => {
+                                    Ok(serde_json::ser::to_string_pretty(
+                                        &ser::JsMoveStructWrapper::new(height, &result),
+                                    )?)
+                                }
+                                OutputFormat::Json => serde_json::ser::to_string_pretty(
+                                    &ser::AnnotatedMoveStructWrapper { height, result },
+                                )
+                                .map_err(|err| anyhow!("{}", err)),
+                                OutputFormat::Yaml => Ok(to_yaml(
+                                    &result.type_,
+                                    &height,
+                                    &render(&result, addresses),
+                                )),
+                                OutputFormat::Table => Ok(to_table(&render(&result, addresses))),
+                                OutputFormat::Csv => Ok(to_csv(&render(&result, addresses))),
+                            }
+                        })
+                        .map(|result| write_output(self.output.as_deref(), &result, "result"))
+                } else {
+                    bail!("Resource not found, result is empty")
+                }
+            })
+            .and_then(|result| result)
+    }
+
+    /// JSON of the resource: annotated or JS-safe with --js-safe.
+    fn json_value(
+        &self,
+        height: String,
+        result: AnnotatedMoveStruct,
+    ) -> anyhow::Result<serde_json::Value> {
+        Ok(if self.js_safe {
+            serde_json::to_value(&ser::JsMoveStructWrapper::new(height, &result))?
+        } else {
+            serde_json::to_value(&ser::AnnotatedMoveStructWrapper { height, result })?
+        })
+    }
+
+    /// Output format. --json is a shortcut for --format json.
+    fn output_format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else {
+            self.format
         }
-        let query = parse_query(&address_map, &self.query)?;
+    }
 
-        match query {
-            TypeTag::Struct(st) => {
-                let addr = st.address;
-
-                net.get_resource(&addr, &st, &height)
-                    .map(|resp| {
-                        let view = NetView::new(net, height);
-                        if let Some(bytes_for_block) = resp {
-                            // Internally produce FatStructType (with layout) for StructTag by
-                            // resolving & de-.. entire deps-chain.
-                            let annotator = move_resource_viewer::MoveValueAnnotator::new(&view);
-
-                            annotator
-                                .view_resource(&st, &bytes_for_block.0)
-                                .and_then(|result| {
-                                    let height = bytes_for_block.1;
-
-                                    if self.json {
-                                        serde_json::ser::to_string_pretty(
-                                            &ser::AnnotatedMoveStructWrapper { height, result },
-                                        )
-                                        .map_err(|err| anyhow!("{}", err))
-                                    } else {
-                                        Ok(format!("{}", result))
-                                    }
-                                })
-                                .map(|result| {
-                                    write_output(self.output.as_deref(), &result, "result")
-                                })
-                        } else {
-                            bail!("Resource not found, result is empty")
-                        }
-                    })
-                    .and_then(|result| result)
+    /// Output format of the resource list and the resource history: text or JSON.
+    fn list_format(&self) -> anyhow::Result<OutputFormat> {
+        match self.output_format() {
+            format @ (OutputFormat::Text | OutputFormat::Json) => Ok(format),
+            format => bail!(
+                "--all, --from and --diff support only text and json output. Got: {:?}",
+                format
+            ),
+        }
+    }
+
+    fn address_format(&self, ctx: &Context) -> anyhow::Result<AddressFormat> {
+        if self.address_format == "ss58" {
+            Ok(AddressFormat::Ss58(prefix(&ctx.project_root_dir, &None)?))
+        } else {
+            Ok(AddressFormat::Hex)
+        }
+    }
+
+    /// The account that holds the resource and the resource type.
+    fn resource(&self, ctx: &Context) -> anyhow::Result<(AccountAddress, StructTag)> {
+        let resolver = ctx.address_resolver();
+        let query = self
+            .query
+            .as_deref()
+            .ok_or_else(|| anyhow!("Specify the query or --all"))?;
+        let (account, query) = split_account(query);
+        let account = match (account, self.account.as_deref()) {
+            (Some(_), Some(_)) => {
+                bail!("The account is specified both in the query and in --account")
             }
+            (account, option) => account.or(option),
+        };
+        let account = account
+            .map(|account| resolver.resolve_account(account))
+            .transpose()?;
+        let query = parse_query(&resolver, query)?;
+
+        match query {
+            TypeTag::Struct(st) => Ok((account.unwrap_or(st.address), st)),
             TypeTag::Vector(list_types) => bail!("Unsupported root type Vec {:?}", list_types),
             _ => bail!("Unsupported type {}", query),
         }
     }
+
+    /// Fetches the resource at the blocks and prints the changes between the consecutive snapshots.
+    fn view_history(&self, ctx: &Context) -> anyhow::Result<()> {
+        let format = self.list_format()?;
+        let heights = match (&self.diff, self.from, self.to) {
+            (Some(heights), _, _) => heights.to_owned(),
+            (None, Some(from), Some(to)) => {
+                ensure!(from <= to, "--from must not be greater than --to");
+                ensure!(self.step > 0, "--step must be positive");
+                ensure!(
+                    (to - from) / self.step < MAX_SNAPSHOTS,
+                    "Too many blocks: at most {} snapshots are allowed. Increase --step",
+                    MAX_SNAPSHOTS
+                );
+                let mut heights = (from..=to)
+                    .step_by(self.step as usize)
+                    .map(|height| height.to_string())
+                    .collect::<Vec<_>>();
+                if heights.last() != Some(&to.to_string()) {
+                    heights.push(to.to_string());
+                }
+                heights
+            }
+            _ => bail!("Specify --from and --to or --diff"),
+        };
+
+        let (addr, st) = self.resource(ctx)?;
+        let addresses = self.address_format(ctx)?;
+        let mut view = NetView::new(self.net(ctx)?, None);
+        let mut previous: Option<Vec<(String, String)>> = None;
+        let mut snapshots = Vec::with_capacity(heights.len());
+        for height in heights {
+            view.set_block(Some(height.clone()));
+            let fields = match ResourceResolver::get_resource(&view, &addr, &st)? {
+                Some(bytes) => {
+                    let resource = MoveValueAnnotator::new(&view).view_resource(&st, &bytes)?;
+                    Some(flatten(&render(&resource, addresses)))
+                }
+                None => None,
+            };
+            let changes = diff(
+                previous.as_deref().unwrap_or_default(),
+                fields.as_deref().unwrap_or_default(),
+            );
+            snapshots.push(Snapshot {
+                height,
+                exists: fields.is_some(),
+                changes,
+            });
+            previous = fields;
+        }
+
+        let result = if format == OutputFormat::Json {
+            serde_json::ser::to_string_pretty(&snapshots)?
+        } else {
+            let mut result = format!("{}@{}", addr.to_hex_literal(), st);
+            let mut existed = false;
+            for (index, snapshot) in snapshots.iter().enumerate() {
+                let status = match (existed, snapshot.exists) {
+                    (false, false) => "not found",
+                    (true, false) => "deleted",
+                    (false, true) if index > 0 => "created",
+                    _ if index > 0 && snapshot.changes.is_empty() => "no changes",
+                    _ => "",
+                };
+                result.push_str(&format!("\nHeight {}:", snapshot.height));
+                if !status.is_empty() {
+                    result.push_str(&format!(" {}", status));
+                }
+                if snapshot.exists {
+                    for change in &snapshot.changes {
+                        result.push_str(&format!("\n    {}", change));
+                    }
+                }
+                existed = snapshot.exists;
+            }
+            result
+        };
+        write_output(self.output.as_deref(), &result, "result");
+        Ok(())
+    }
+
+    /// The node or the local sandbox storage.
+    fn net(&self, ctx: &Context) -> anyhow::Result<Box<dyn Net>> {
+        if self.local {
+            Ok(Box::new(SandboxNet::new(ctx)?))
+        } else {
+            let cache = if self.no_cache {
+                None
+            } else {
+                Some(module_cache(Duration::from_secs(self.cache_ttl))?)
+            };
+            make_cached_net(self.api.clone(), cache)
+        }
+    }
+
+    /// Lists all resources stored under the account in the local sandbox.
+    fn view_all(&self, ctx: &Context) -> anyhow::Result<()> {
+        let format = self.list_format()?;
+        let account = self
+            .account
+            .as_deref()
+            .ok_or_else(|| anyhow!("Specify the account"))?;
+        let address = ctx.address_resolver().resolve_account(account)?;
+        let height = self.height.clone();
+        let net = SandboxNet::new(ctx)?;
+        let resources = net.list_resources(&address, &height)?;
+
+        let view = NetView::new(Box::new(net), height);
+        let annotator = MoveValueAnnotator::new(&view);
+        let resources = resources
+            .into_iter()
+            .map(|(tag, bytes_for_block)| {
+                annotator
+                    .view_resource(&tag, &bytes_for_block.0)
+                    .map(|result| (bytes_for_block.1, result))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let result = if format == OutputFormat::Json {
+            let resources = resources
+                .into_iter()
+                .map(|(height, result)| self.json_value(height, result))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            serde_json::ser::to_string_pretty(&resources)?
+        } else {
+            let mut table = format!(
+                "Resources of {}: {}",
+                address.to_hex_literal(),
+                resources.len()
+            );
+            let addresses = self.address_format(ctx)?;
+            for (_, resource) in &resources {
+                let tag = resource.type_.to_string();
+                let fields = summary(&render(resource, addresses));
+                table.push_str(&format!("\n    {:<50} {}", tag, fields));
+            }
+            table
+        };
+        write_output(self.output.as_deref(), &result, "result");
+        Ok(())
+    }
 }
 
-fn produce_json_schema(path: &Path) {
-    let schema = ser::produce_json_schema();
+fn produce_json_schema(path: &Path, js_safe: bool) {
+    let schema = if js_safe {
+        ser::produce_js_json_schema()
+    } else {
+        ser::produce_json_schema()
+    };
     let render = serde_json::to_string_pretty(&schema).unwrap();
     write_output(Some(path), &render, "schema");
 }
@@ -148,21 +506,55 @@ fn write_output(path: Option<&Path>, result: &str, name: &str) {
     }
 }
 
+/// Splits the query into the account that holds the resource and the resource type.
+///     //Alice@0x1::Account::Balance<0x1::PONT::PONT> => (Some("//Alice"), "0x1::Account::Balance<0x1::PONT::PONT>")
+pub(crate) fn split_account(query: &str) -> (Option<&str>, &str) {
+    match query.split_once('@') {
+        Some((account, query)) => (Some(account.trim()), query.trim()),
+        None => (None, query),
+    }
+}
+
 /// Query parsing
-///     addr_map:&AddressDeclarations - To check alias addresses and replace with a hexadecimal address
+///     resolver:&AddressResolver - To resolve named, SS58 and dev account addresses
 ///     qyery - Query string for parsing
-fn parse_query(addr_map: &AddressDeclarations, query: &str) -> Result<TypeTag, Error> {
+pub(crate) fn parse_query(resolver: &AddressResolver, query: &str) -> Result<TypeTag, Error> {
     use move_command_line_common::files::FileHash;
     use move_compiler::shared::CompilationEnv;
     use move_compiler::parser::syntax::Context;
     use move_compiler::parser::lexer::Lexer;
     use move_compiler::Flags;
 
-    let mut lexer = Lexer::new(query, FileHash::new(query));
+    let query = resolver.replace_leading_address(query)?;
+    let mut lexer = Lexer::new(&query, FileHash::new(&query));
     let mut env = CompilationEnv::new(Flags::empty(), Default::default());
     let mut ctx = Context::new(&mut env, &mut lexer);
 
     ctx.tokens.advance().map_err(|err| anyhow!("{:?}", &err))?;
 
-    parse_type_param(addr_map, &mut ctx)
+    parse_type_param(resolver, &mut ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_account;
+
+    #[test]
+    fn test_split_account() {
+        assert_eq!(
+            split_account("0x1::Account::Balance<0x1::PONT::PONT>"),
+            (None, "0x1::Account::Balance<0x1::PONT::PONT>")
+        );
+        assert_eq!(
+            split_account("//Alice@0x1::Account::Balance<0x1::PONT::PONT>"),
+            (Some("//Alice"), "0x1::Account::Balance<0x1::PONT::PONT>")
+        );
+        assert_eq!(
+            split_account("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY @ Store::U64"),
+            (
+                Some("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+                "Store::U64"
+            )
+        );
+    }
 }
@@ -0,0 +1,512 @@
+use std::fmt;
+use anyhow::{Error, Result};
+
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::compatibility::Compatibility;
+use move_binary_format::CompiledModule;
+use move_binary_format::file_format::{AbilitySet, Visibility};
+use move_binary_format::normalized::{Function, Module, Struct, Type};
+use move_core_types::language_storage::ModuleId;
+use lang::bytecode::info::TypeAbilities;
+use net::Net;
+
+use crate::bundle::module_id_to_string;
+
+/// Change of the module that breaks the modules and the data already on chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Incompatibility {
+    /// The struct was removed or its fields or type parameters were changed.
+    Layout(String),
+    /// The struct abilities were changed.
+    Abilities(String),
+    /// The public, script or friend function was removed or its signature or visibility was changed.
+    Linking(String),
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Incompatibility::Layout(change) => write!(f, "layout: {}", change),
+            Incompatibility::Abilities(change) => write!(f, "abilities: {}", change),
+            Incompatibility::Linking(change) => write!(f, "linking: {}", change),
+        }
+    }
+}
+
+/// Compares the new version of the module with the old one.
+/// New structs and functions are compatible, everything else that other modules
+/// or the stored resources rely on must stay the same.
+pub fn check_compatibility(old: &CompiledModule, new: &CompiledModule) -> Vec<Incompatibility> {
+    compare(&Module::new(old), &Module::new(new))
+}
+
+/// Lists the incompatible changes of the normalized modules.
+/// The VM compatibility rules have the final word: a change they reject is reported
+/// even if it is not described above.
+fn compare(old: &Module, new: &Module) -> Vec<Incompatibility> {
+    let mut changes = Vec::new();
+
+    for (name, old_struct) in &old.structs {
+        let new_struct = match new.structs.get(name) {
+            Some(new_struct) => new_struct,
+            None => {
+                changes.push(Incompatibility::Layout(format!(
+                    "struct {} was removed",
+                    name
+                )));
+                continue;
+            }
+        };
+        if old_struct.abilities != new_struct.abilities {
+            changes.push(Incompatibility::Abilities(format!(
+                "struct {}: {} -> {}",
+                name,
+                abilities_to_string(old_struct.abilities),
+                abilities_to_string(new_struct.abilities)
+            )));
+        }
+        if old_struct.type_parameters != new_struct.type_parameters {
+            changes.push(Incompatibility::Layout(format!(
+                "struct {}: type parameters were changed",
+                name
+            )));
+        }
+        if old_struct.fields != new_struct.fields {
+            changes.push(Incompatibility::Layout(format!(
+                "struct {}: {{ {} }} -> {{ {} }}",
+                name,
+                fields_to_string(old_struct),
+                fields_to_string(new_struct)
+            )));
+        }
+    }
+
+    for (name, old_func) in &old.exposed_functions {
+        let new_func = match new.exposed_functions.get(name) {
+            Some(new_func) => new_func,
+            None => {
+                changes.push(Incompatibility::Linking(format!(
+                    "{} function {} was removed",
+                    visibility_to_string(old_func.visibility),
+                    name
+                )));
+                continue;
+            }
+        };
+        if !is_visibility_compatible(old_func.visibility, new_func.visibility) {
+            changes.push(Incompatibility::Linking(format!(
+                "function {}: {} -> {}",
+                name,
+                visibility_to_string(old_func.visibility),
+                visibility_to_string(new_func.visibility)
+            )));
+        }
+        if old_func.parameters != new_func.parameters
+            || old_func.return_ != new_func.return_
+            || old_func.type_parameters != new_func.type_parameters
+        {
+            changes.push(Incompatibility::Linking(format!(
+                "function {}: {} -> {}",
+                name,
+                signature_to_string(old_func),
+                signature_to_string(new_func)
+            )));
+        }
+    }
+
+    if changes.is_empty() {
+        let compatibility = Compatibility::check(old, new);
+        if !compatibility.struct_layout {
+            changes.push(Incompatibility::Layout(
+                "the struct layout is incompatible".to_string(),
+            ));
+        }
+        if !compatibility.struct_and_function_linking {
+            changes.push(Incompatibility::Linking(
+                "the module is incompatible with its dependents".to_string(),
+            ));
+        }
+    }
+
+    changes
+}
+
+/// The VM rule: the visibility must stay the same, only a friend function can become public.
+/// Friend functions are checked as the public ones: the friend modules on chain link against them.
+fn is_visibility_compatible(old: Visibility, new: Visibility) -> bool {
+    old == new || (old == Visibility::Friend && new == Visibility::Public)
+}
+
+/// Status of the module compared with the deployed one.
+#[derive(Debug)]
+pub enum ModuleStatus {
+    /// The module is not on chain.
+    New,
+    /// The bytecode is the same.
+    Unchanged,
+    /// The bytecode differs. Contains the incompatible changes.
+    Changed(Vec<Incompatibility>),
+}
+
+/// Module compared with the deployed one.
+#[derive(Debug)]
+pub struct ModulePlan {
+    pub id: ModuleId,
+    pub bytecode: Vec<u8>,
+    pub status: ModuleStatus,
+}
+
+impl ModulePlan {
+    /// Returns true if the module needs to be published.
+    pub fn need_to_publish(&self) -> bool {
+        !matches!(self.status, ModuleStatus::Unchanged)
+    }
+
+    /// Returns true if the module can't replace the deployed one.
+    pub fn is_incompatible(&self) -> bool {
+        matches!(&self.status, ModuleStatus::Changed(changes) if !changes.is_empty())
+    }
+}
+
+/// Compares the modules with their deployed versions.
+pub fn compare_with_chain(net: &dyn Net, modules: &[Vec<u8>]) -> Result<Vec<ModulePlan>> {
+    compare_modules(modules, |id| {
+        net.get_module(id, &None)
+            .map(|bytecode| bytecode.map(|bytecode| bytecode.0))
+    })
+}
+
+/// Compares the modules with the baseline versions.
+/// `baseline` returns the bytecode of the module version to compare with.
+pub fn compare_modules<F>(modules: &[Vec<u8>], baseline: F) -> Result<Vec<ModulePlan>>
+where
+    F: Fn(&ModuleId) -> Result<Option<Vec<u8>>>,
+{
+    modules
+        .iter()
+        .map(|bytecode| {
+            let module = deserialize(bytecode)?;
+            let id = module.self_id();
+            let status = match baseline(&id)? {
+                None => ModuleStatus::New,
+                Some(old) if old == *bytecode => ModuleStatus::Unchanged,
+                Some(old) => {
+                    ModuleStatus::Changed(check_compatibility(&deserialize(&old)?, &module))
+                }
+            };
+            Ok(ModulePlan {
+                id,
+                bytecode: bytecode.to_owned(),
+                status,
+            })
+        })
+        .collect()
+}
+
+/// Prints the deploy plan.
+pub fn print_plan(plan: &[ModulePlan]) {
+    println!("Deploy plan:");
+    print_modules(plan);
+}
+
+/// Prints the status and the incompatible changes of each module.
+pub fn print_modules(plan: &[ModulePlan]) {
+    for module in plan {
+        let status = match &module.status {
+            ModuleStatus::New => "new",
+            ModuleStatus::Unchanged => "unchanged",
+            ModuleStatus::Changed(_) if module.is_incompatible() => "incompatible",
+            ModuleStatus::Changed(_) => "changed",
+        };
+        println!("    {:<12} {}", status, module_id_to_string(&module.id));
+        if let ModuleStatus::Changed(changes) = &module.status {
+            for change in changes {
+                println!("        {}", change);
+            }
+        }
+    }
+}
+
+pub(crate) fn deserialize(bytecode: &[u8]) -> Result<CompiledModule, Error> {
+    CompiledModule::deserialize(bytecode)
+        .map_err(|err| anyhow!("Failed to deserialize move module: {:?}", err))
+}
+
+pub(crate) fn abilities_to_string(abilities: AbilitySet) -> String {
+    let abilities = TypeAbilities::from(&abilities).to_string();
+    if abilities.is_empty() {
+        "no abilities".to_string()
+    } else {
+        abilities
+    }
+}
+
+fn fields_to_string(st: &Struct) -> String {
+    st.fields
+        .iter()
+        .map(|field| format!("{}: {}", field.name, type_to_string(&field.type_)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub(crate) fn signature_to_string(func: &Function) -> String {
+    let type_parameters = if func.type_parameters.is_empty() {
+        String::new()
+    } else {
+        let type_parameters = func
+            .type_parameters
+            .iter()
+            .enumerate()
+            .map(|(index, abilities)| {
+                if abilities == &AbilitySet::EMPTY {
+                    format!("T{}", index)
+                } else {
+                    format!("T{}: {}", index, TypeAbilities::from(abilities))
+                }
+            })
+            .collect::<Vec<_>>();
+        format!("<{}>", type_parameters.join(", "))
+    };
+    let types = |types: &[Type]| {
+        types
+            .iter()
+            .map(type_to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let returns = match func.return_.len() {
+        0 => String::new(),
+        1 => format!(": {}", type_to_string(&func.return_[0])),
+        _ => format!(": ({})", types(&func.return_)),
+    };
+    format!(
+        "{}({}){}",
+        type_parameters,
+        types(&func.parameters),
+        returns
+    )
+}
+
+fn visibility_to_string(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Private => "private",
+        Visibility::Public => "public",
+        Visibility::Script => "script",
+        Visibility::Friend => "friend",
+    }
+}
+
+fn type_to_string(tp: &Type) -> String {
+    match tp {
+        Type::Bool => "bool".to_string(),
+        Type::U8 => "u8".to_string(),
+        Type::U64 => "u64".to_string(),
+        Type::U128 => "u128".to_string(),
+        Type::Address => "address".to_string(),
+        Type::Signer => "signer".to_string(),
+        Type::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => {
+            let mut st = format!("{}::{}::{}", address.to_hex_literal(), module, name);
+            if !type_arguments.is_empty() {
+                let type_arguments = type_arguments
+                    .iter()
+                    .map(type_to_string)
+                    .collect::<Vec<_>>();
+                st.push_str(&format!("<{}>", type_arguments.join(", ")));
+            }
+            st
+        }
+        Type::Vector(tp) => format!("vector<{}>", type_to_string(tp)),
+        Type::TypeParameter(index) => format!("T{}", index),
+        Type::Reference(tp) => format!("&{}", type_to_string(tp)),
+        Type::MutableReference(tp) => format!("&mut {}", type_to_string(tp)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use move_binary_format::file_format::{empty_module, Ability, AbilitySet, Visibility};
+    use move_binary_format::normalized::{Field, Function, Module, Struct, Type};
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+    use super::{compare, type_to_string, Incompatibility};
+
+    fn id(name: &str) -> Identifier {
+        Identifier::new(name).unwrap()
+    }
+
+    fn coin(abilities: AbilitySet, value: Type) -> Struct {
+        Struct {
+            abilities,
+            type_parameters: vec![],
+            fields: vec![Field {
+                name: id("value"),
+                type_: value,
+            }],
+        }
+    }
+
+    fn function(visibility: Visibility, parameters: Vec<Type>) -> Function {
+        Function {
+            visibility,
+            type_parameters: vec![],
+            parameters,
+            return_: vec![Type::U64],
+        }
+    }
+
+    /// Module with the struct Coin { value: u64 } has store,
+    /// public fun value(u64): u64 and friend fun mint(u64): u64
+    fn module() -> Module {
+        let mut module = Module::new(&empty_module());
+        module.structs.insert(
+            id("Coin"),
+            coin(AbilitySet::EMPTY.add(Ability::Store), Type::U64),
+        );
+        module
+            .exposed_functions
+            .insert(id("value"), function(Visibility::Public, vec![Type::U64]));
+        module
+            .exposed_functions
+            .insert(id("mint"), function(Visibility::Friend, vec![Type::U64]));
+        module
+    }
+
+    #[test]
+    fn test_compatible() {
+        assert!(compare(&module(), &module()).is_empty());
+
+        let mut new = module();
+        new.structs
+            .insert(id("Vault"), coin(AbilitySet::EMPTY, Type::U8));
+        new.exposed_functions
+            .insert(id("run"), function(Visibility::Script, vec![]));
+        new.exposed_functions
+            .insert(id("mint"), function(Visibility::Public, vec![Type::U64]));
+        assert!(compare(&module(), &new).is_empty());
+    }
+
+    #[test]
+    fn test_layout() {
+        let mut new = module();
+        new.structs.insert(
+            id("Coin"),
+            coin(AbilitySet::EMPTY.add(Ability::Store), Type::U128),
+        );
+        assert_eq!(
+            compare(&module(), &new),
+            vec![Incompatibility::Layout(
+                "struct Coin: { value: u64 } -> { value: u128 }".to_string()
+            )]
+        );
+
+        let mut new = module();
+        new.structs.remove(&id("Coin"));
+        assert_eq!(
+            compare(&module(), &new),
+            vec![Incompatibility::Layout(
+                "struct Coin was removed".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_abilities() {
+        let mut new = module();
+        new.structs.insert(
+            id("Coin"),
+            coin(
+                AbilitySet::EMPTY.add(Ability::Store).add(Ability::Key),
+                Type::U64,
+            ),
+        );
+        let changes = compare(&module(), &new);
+        assert_eq!(changes.len(), 1);
+        match &changes[0] {
+            Incompatibility::Abilities(change) => assert!(change.starts_with("struct Coin: ")),
+            change => panic!("Unexpected change: {}", change),
+        }
+    }
+
+    #[test]
+    fn test_linking() {
+        let mut new = module();
+        new.exposed_functions.remove(&id("value"));
+        assert_eq!(
+            compare(&module(), &new),
+            vec![Incompatibility::Linking(
+                "public function value was removed".to_string()
+            )]
+        );
+
+        let mut new = module();
+        new.exposed_functions
+            .insert(id("value"), function(Visibility::Script, vec![Type::U64]));
+        assert_eq!(
+            compare(&module(), &new),
+            vec![Incompatibility::Linking(
+                "function value: public -> script".to_string()
+            )]
+        );
+
+        let mut new = module();
+        new.exposed_functions
+            .insert(id("value"), function(Visibility::Public, vec![Type::U128]));
+        assert_eq!(
+            compare(&module(), &new),
+            vec![Incompatibility::Linking(
+                "function value: (u64): u64 -> (u128): u64".to_string()
+            )]
+        );
+
+        let mut new = module();
+        new.exposed_functions.remove(&id("mint"));
+        assert_eq!(
+            compare(&module(), &new),
+            vec![Incompatibility::Linking(
+                "friend function mint was removed".to_string()
+            )]
+        );
+
+        let mut new = module();
+        new.exposed_functions
+            .insert(id("mint"), function(Visibility::Friend, vec![]));
+        assert_eq!(
+            compare(&module(), &new),
+            vec![Incompatibility::Linking(
+                "function mint: (u64): u64 -> (): u64".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_type_to_string() {
+        let coin = Type::Struct {
+            address: AccountAddress::from_hex_literal("0x1").unwrap(),
+            module: Identifier::new("Coins").unwrap(),
+            name: Identifier::new("Coin").unwrap(),
+            type_arguments: vec![Type::TypeParameter(0)],
+        };
+        assert_eq!(type_to_string(&coin), "0x1::Coins::Coin<T0>");
+        assert_eq!(
+            type_to_string(&Type::Vector(Box::new(Type::U8))),
+            "vector<u8>"
+        );
+        assert_eq!(
+            type_to_string(&Type::MutableReference(Box::new(coin))),
+            "&mut 0x1::Coins::Coin<T0>"
+        );
+    }
+
+    #[test]
+    fn test_incompatibility_display() {
+        assert_eq!(
+            Incompatibility::Layout("struct T was removed".to_string()).to_string(),
+            "layout: struct T was removed"
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::fs;
 use std::fs::read_to_string;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::{Error, Result};
 
 use move_cli::Move;
@@ -10,6 +10,8 @@ use move_package::source_package::parsed_manifest::{AddressDeclarations, SourceM
 use move_core_types::errmap::ErrorMapping;
 use move_core_types::gas_schedule::CostTable;
 use move_vm_runtime::native_functions::NativeFunctionTable;
+use lang::address::AddressResolver;
+use crate::publish::wallet_key_address;
 
 pub struct Context {
     pub project_root_dir: PathBuf,
@@ -28,11 +30,7 @@ impl Context {
         native_functions: NativeFunctionTable,
         cost_table: CostTable,
     ) -> Result<Self> {
-        let manifest_string =
-            read_to_string(project_root_dir.join(layout::SourcePackageLayout::Manifest.path()))
-                .map_err(|_| anyhow!("Move.toml not found. Path: {:?}", &project_root_dir))?;
-        let toml_manifest = manifest_parser::parse_move_manifest_string(manifest_string)?;
-        let manifest = manifest_parser::parse_source_manifest(toml_manifest)?;
+        let manifest = read_manifest(&project_root_dir)?;
 
         Ok(Context {
             project_root_dir,
@@ -79,6 +77,13 @@ impl Context {
         self.manifest.addresses.clone().unwrap_or_default()
     }
 
+    /// Returns the address resolver with the named addresses.
+    /// Wallet keys are used only for the accounts: see `AddressResolver::resolve_account`.
+    pub fn address_resolver(&self) -> AddressResolver {
+        AddressResolver::new(self.address_declarations())
+            .with_aliases(Box::new(wallet_key_address))
+    }
+
     /// Returns transaction output folder for specified `package` or for the default package.
     pub fn tx_output_path(&self, pac: Option<String>) -> PathBuf {
         let mut build = self
@@ -92,3 +97,13 @@ impl Context {
         build.join("transaction")
     }
 }
+
+/// Reads and parses the project manifest.
+///     ./Move.toml
+pub fn read_manifest(project_root_dir: &Path) -> Result<SourceManifest> {
+    let manifest_string =
+        read_to_string(project_root_dir.join(layout::SourcePackageLayout::Manifest.path()))
+            .map_err(|_| anyhow!("Move.toml not found. Path: {:?}", &project_root_dir))?;
+    let toml_manifest = manifest_parser::parse_move_manifest_string(manifest_string)?;
+    manifest_parser::parse_source_manifest(toml_manifest)
+}
@@ -0,0 +1,209 @@
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::{Serialize, Deserialize};
+use diem_crypto::HashValue;
+use move_core_types::account_address::AccountAddress;
+
+use crate::bundle::ModuleHash;
+use crate::{DOVE_VERSION, DOVE_HASH};
+
+/// Registry file in the project directory.
+pub const REGISTRY_FILE: &str = "deployments.toml";
+
+/// Published bundle, module or executed transaction.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Deployment {
+    /// bundle, module or call
+    pub kind: String,
+    /// Bundle, module or script function name.
+    pub name: String,
+    /// Url of the node.
+    pub network: String,
+    /// Address of the account that signed the transaction.
+    pub signer: String,
+    /// SHA3-256 of the published file.
+    pub hash: String,
+    /// SHA3-256 of the source file if only a part of it was published:
+    /// the bundle without the modules that are already on chain.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub source_hash: String,
+    /// Hash returned by the node.
+    pub tx_hash: String,
+    /// UTC time: 2021-12-01T10:00:00Z
+    pub timestamp: String,
+    /// Dove version and commit.
+    pub dove_version: String,
+    /// Modules of the bundle.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modules: Vec<ModuleHash>,
+}
+
+impl Deployment {
+    /// Creates the record of the deployment made now by the current dove.
+    pub fn new(kind: &str, name: &str, network: &str, signer: &str, bytes: &[u8]) -> Deployment {
+        Deployment {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            network: network.to_string(),
+            signer: signer.to_string(),
+            hash: HashValue::sha3_256_of(bytes).to_hex(),
+            source_hash: String::new(),
+            tx_hash: String::new(),
+            timestamp: utc_now(),
+            dove_version: format!("{}-{}", DOVE_VERSION, DOVE_HASH),
+            modules: Vec::new(),
+        }
+    }
+}
+
+/// History of the project deployments.
+///     ./deployments.toml
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Registry {
+    #[serde(default, rename = "deployment")]
+    pub deployments: Vec<Deployment>,
+}
+
+impl Registry {
+    /// Loads the registry of the project. Returns an empty registry if the file does not exist.
+    pub fn load(project_dir: &Path) -> Result<Registry> {
+        let path = project_dir.join(REGISTRY_FILE);
+        if !path.exists() {
+            return Ok(Registry::default());
+        }
+        toml::from_str(&fs::read_to_string(&path)?)
+            .map_err(|err| anyhow!("Failed to read {:?}: {}", path, err))
+    }
+
+    /// Appends the deployment to the registry of the project.
+    pub fn record(project_dir: &Path, deployment: Deployment) -> Result<()> {
+        let mut registry = Registry::load(project_dir)?;
+        registry.deployments.push(deployment);
+        fs::write(project_dir.join(REGISTRY_FILE), toml::to_string(&registry)?)?;
+        Ok(())
+    }
+
+    /// Returns the number (starting from 1) and the deployment of the same file to the network.
+    /// The file is compared with the published files and with the sources of the partial publications.
+    pub fn find(&self, network: &str, hash: &str) -> Option<(usize, &Deployment)> {
+        self.deployments
+            .iter()
+            .enumerate()
+            .find(|(_, deployment)| {
+                deployment.network == network
+                    && (deployment.hash == hash || deployment.source_hash == hash)
+            })
+            .map(|(index, deployment)| (index + 1, deployment))
+    }
+}
+
+/// Signer address or "unknown" if the address can't be determined.
+pub fn signer_name(address: Result<AccountAddress>) -> String {
+    address
+        .map(|address| address.to_hex_literal())
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Current UTC time: 2021-12-01T10:00:00Z
+fn utc_now() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    utc_time(seconds)
+}
+
+/// Formats the unix time. Converts the days to the civil date of the proleptic Gregorian calendar.
+fn utc_time(seconds: u64) -> String {
+    let (days, seconds) = ((seconds / 86400) as i64, seconds % 86400);
+    let days = days + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use super::{utc_time, Deployment, Registry};
+
+    #[test]
+    fn test_utc_time() {
+        assert_eq!(utc_time(0), "1970-01-01T00:00:00Z");
+        assert_eq!(utc_time(951782400), "2000-02-29T00:00:00Z");
+        assert_eq!(utc_time(1638352800), "2021-12-01T10:00:00Z");
+    }
+
+    #[test]
+    fn test_registry_toml() {
+        let mut deployment =
+            Deployment::new("bundle", "demo", "ws://localhost:9944", "0x1", b"1");
+        deployment.tx_hash = "0x01".to_string();
+        let registry = Registry {
+            deployments: vec![deployment.clone(), deployment],
+        };
+        let registry: Registry = toml::from_str(&toml::to_string(&registry).unwrap()).unwrap();
+        assert_eq!(registry.deployments.len(), 2);
+        let hash = registry.deployments[0].hash.to_owned();
+        assert_eq!(registry.find("ws://localhost:9944", &hash).unwrap().0, 1);
+        assert!(registry.find("ws://127.0.0.1:9944", &hash).is_none());
+    }
+
+    #[test]
+    fn test_find_partial_deployment() {
+        let mut deployment =
+            Deployment::new("bundle", "demo", "ws://localhost:9944", "0x1", b"changed");
+        deployment.source_hash = "01".to_string();
+        let registry = Registry {
+            deployments: vec![deployment],
+        };
+        assert_eq!(registry.find("ws://localhost:9944", "01").unwrap().0, 1);
+        assert!(registry.find("ws://localhost:9944", "02").is_none());
+    }
+
+    #[test]
+    fn test_registry_record() {
+        let dir = std::env::temp_dir().join(format!("dove_registry_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        Registry::record(
+            &dir,
+            Deployment::new("bundle", "demo", "ws://localhost:9944", "0x1", b"1"),
+        )
+        .unwrap();
+        Registry::record(
+            &dir,
+            Deployment::new("call", "init", "ws://localhost:9944", "0x1", b"2"),
+        )
+        .unwrap();
+        let registry = Registry::load(&dir).unwrap();
+        assert_eq!(
+            registry
+                .deployments
+                .iter()
+                .map(|deployment| deployment.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["demo", "init"]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -18,18 +18,32 @@ extern crate anyhow;
 
 use std::fs::create_dir;
 use std::path::PathBuf;
+use std::time::Duration;
 use anyhow::Result;
+use net::CacheConfig;
 
+/// Bundle format.
+pub mod bundle;
 /// Transactions.
 pub mod call;
 /// Dove cli interface.
 pub mod cli;
 /// Dove commands handler.
 pub mod cmd;
+/// Module compatibility.
+pub mod compat;
 /// Dove execution context.
 pub mod context;
+/// Deployment registry.
+pub mod deployments;
 /// Native functions.
 pub mod natives;
+/// Rendering of the resources for `dove view`.
+pub mod render;
+/// Changes of the resources between the snapshots.
+pub mod resource_diff;
+/// Local sandbox storage as the network.
+pub mod sandbox;
 /// To work with stored access keys
 pub mod wallet_key;
 
@@ -51,3 +65,12 @@ pub fn dot_move_folder() -> Result<PathBuf> {
     }
     Ok(move_home_path)
 }
+
+/// Cache of the node modules: ~/.move/cache/modules
+/// Removed by `dove clean --global`.
+pub fn module_cache(ttl: Duration) -> Result<CacheConfig> {
+    Ok(CacheConfig {
+        dir: dot_move_folder()?.join("cache").join("modules"),
+        ttl,
+    })
+}
@@ -1,11 +1,18 @@
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use anyhow::Error;
 use structopt::StructOpt;
 use anyhow::Result;
 use url::Url;
 
+use move_core_types::account_address::AccountAddress;
+
+use lang::address::dev_account;
+use lang::ss58::ss58_to_address;
 use pontem_client::PontemClient;
+use crate::bundle::Bundle;
 use crate::cmd::key::cli_entering_a_secret_phrase;
 use crate::wallet_key;
 use crate::wallet_key::WalletKey;
@@ -32,21 +39,87 @@ pub struct NodeAccessParams {
     )]
     url_to_node: Url,
 
-    /// Limitation of gas consumption per operation
+    /// Limitation of gas consumption per operation.
+    /// Use `auto[:MULTIPLIER]` to estimate it by the local simulation (dove call only).
     #[structopt(long = "gas", short)]
-    gas_limit: Option<u64>,
+    gas_limit: Option<GasLimit>,
 }
 
 impl NodeAccessParams {
     pub fn need_to_publish(&self) -> bool {
         self.account.is_some() || self.secret_phrase
     }
+
+    /// The url of the substrate node.
+    pub fn url(&self) -> &Url {
+        &self.url_to_node
+    }
+
+    /// Account from whom to publish.
+    pub fn account(&self) -> Option<&str> {
+        self.account.as_deref()
+    }
+
+    /// Returns true if the secret phrase is requested instead of the account.
+    pub fn secret_phrase(&self) -> bool {
+        self.secret_phrase
+    }
+
+    pub fn gas_limit(&self) -> Option<GasLimit> {
+        self.gas_limit
+    }
+
+    /// Replaces the automatic gas limit with the estimated one.
+    pub fn resolve_gas_limit(&mut self, gas_used: u64) {
+        if let Some(GasLimit::Auto(multiplier)) = self.gas_limit {
+            self.gas_limit = Some(GasLimit::Fixed((gas_used as f64 * multiplier).ceil() as u64));
+        }
+    }
+}
+
+/// Default multiplier of the estimated gas.
+const DEFAULT_GAS_MULTIPLIER: f64 = 1.2;
+
+/// Gas limit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GasLimit {
+    /// Fixed gas limit.
+    Fixed(u64),
+    /// Gas used by the simulation multiplied by the given factor.
+    Auto(f64),
+}
+
+impl FromStr for GasLimit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "auto" {
+            return Ok(GasLimit::Auto(DEFAULT_GAS_MULTIPLIER));
+        }
+        if let Some(multiplier) = s.strip_prefix("auto:") {
+            let multiplier: f64 = multiplier
+                .parse()
+                .map_err(|_| anyhow!("Invalid gas multiplier: {}", multiplier))?;
+            ensure!(
+                multiplier.is_finite() && multiplier > 0.0,
+                "Gas multiplier must be a positive number"
+            );
+            return Ok(GasLimit::Auto(multiplier));
+        }
+        s.parse()
+            .map(GasLimit::Fixed)
+            .map_err(|_| anyhow!("Expected a number or 'auto[:MULTIPLIER]': {}", s))
+    }
 }
 
 pub struct Publish {
     /// Client for connecting to "Pontem"
     client: PontemClient,
 
+    /// Url of the node
+    node_url: Url,
+
     /// Path to the file to be published
     file_path: PathBuf,
 
@@ -58,6 +131,22 @@ pub struct Publish {
 }
 
 impl Publish {
+    /// Url of the node.
+    pub fn node_url(&self) -> &Url {
+        &self.node_url
+    }
+
+    /// Address of the account that signs the transaction.
+    pub fn signer_address(&self) -> Result<AccountAddress> {
+        signer_address(&self.client, &self.access)
+    }
+
+    /// Replaces the file to publish. The connection and the account are kept:
+    /// the secret phrase and the key password are not requested again.
+    pub fn set_file_path(&mut self, file_path: PathBuf) {
+        self.file_path = file_path;
+    }
+
     pub fn apply(&self) -> Result<String> {
         match self.file_type()? {
             FileType::Module => match &self.access {
@@ -72,18 +161,16 @@ impl Publish {
                     test_account,
                 ),
             },
-            FileType::Bundle => match &self.access {
-                AccessType::SecretPhrase(secret) => self.client.tx_mvm_publish_package(
-                    self.file_path_as_str()?,
-                    self.gas_limit,
-                    secret,
-                ),
-                AccessType::TestAccount(test_account) => self.client.tx_mvm_publish_package_dev(
-                    self.file_path_as_str()?,
-                    self.gas_limit,
-                    test_account,
-                ),
-            },
+            FileType::Bundle => {
+                let legacy_path = self.legacy_bundle()?;
+                let result =
+                    self.publish_package(legacy_path.as_ref().unwrap_or(&self.file_path));
+                // A leftover converted file does not change the result of the publication.
+                if let Some(legacy_path) = legacy_path {
+                    fs::remove_file(legacy_path).ok();
+                }
+                result
+            }
             FileType::TX => match &self.access {
                 AccessType::SecretPhrase(secret) => {
                     self.client
@@ -106,34 +193,11 @@ impl TryFrom<(&NodeAccessParams, PathBuf)> for Publish {
 
     fn try_from(value: (&NodeAccessParams, PathBuf)) -> std::result::Result<Self, Self::Error> {
         let (params, file_path) = value;
-        let gas_limit = params
-            .gas_limit
-            .ok_or(anyhow!("Please specify gas limit"))?;
-        let mut url_to_node = params.url_to_node.clone();
-
-        let access = if params.secret_phrase {
-            // Request secret phrases
-            let secret = cli_entering_a_secret_phrase()?;
-            AccessType::SecretPhrase(secret)
-        } else if let Some(test_account_or_name_key) = &params.account {
-            match cli_name_to_key(test_account_or_name_key)? {
-                Some(WalletKey {
-                    secret_phrase,
-                    node_address,
-                }) => {
-                    url_to_node = node_address;
-                    AccessType::SecretPhrase(secret_phrase)
-                }
-                None => AccessType::TestAccount(test_account_or_name_key.to_owned()),
-            }
-        } else {
-            bail!("Specify name of key or name of test account or secret phrase")
-        };
-
-        let client = PontemClient::new(url_to_node.as_str())?;
+        let (client, node_url, access, gas_limit) = connect(params)?;
 
         Ok(Publish {
             client,
+            node_url,
             access,
             gas_limit,
             file_path,
@@ -141,6 +205,188 @@ impl TryFrom<(&NodeAccessParams, PathBuf)> for Publish {
     }
 }
 
+/// Sequence of transactions executed by one account.
+/// The transactions are executed atomically in one batch extrinsic (`apply_batch`)
+/// or one after another (`apply`).
+pub struct PublishSequence {
+    /// Client for connecting to "Pontem"
+    client: PontemClient,
+
+    /// Url of the node
+    node_url: Url,
+
+    /// Paths to the transaction files
+    file_paths: Vec<PathBuf>,
+
+    /// Limitation of gas consumption per transaction
+    gas_limit: u64,
+
+    /// Access type - by secret phrase or through a test account
+    access: AccessType,
+}
+
+impl PublishSequence {
+    /// Url of the node.
+    pub fn node_url(&self) -> &Url {
+        &self.node_url
+    }
+
+    /// Address of the account that signs the transactions.
+    pub fn signer_address(&self) -> Result<AccountAddress> {
+        signer_address(&self.client, &self.access)
+    }
+
+    /// Executes the transactions in one `Utility.batch_all` extrinsic: all of them or none.
+    /// Returns the hash of the batch extrinsic.
+    pub fn apply_batch(&self) -> Result<String> {
+        let file_paths = self.file_paths_as_str()?;
+        match &self.access {
+            AccessType::SecretPhrase(secret) => {
+                self.client
+                    .tx_mvm_execute_batch(&file_paths, self.gas_limit, secret)
+            }
+            AccessType::TestAccount(test_account) => {
+                self.client
+                    .tx_mvm_execute_batch_dev(&file_paths, self.gas_limit, test_account)
+            }
+        }
+    }
+
+    /// Executes the transactions one after another. The sequence is not atomic:
+    /// it stops at the first failed transaction, and the transactions executed before it stay committed.
+    /// `on_executed` receives the index and the hash of each transaction as soon as it is executed.
+    /// Returns the hashes of the executed transactions.
+    pub fn apply<F>(&self, mut on_executed: F) -> Result<Vec<String>>
+    where
+        F: FnMut(usize, &str),
+    {
+        let file_paths = self.file_paths_as_str()?;
+        let total = file_paths.len();
+        let mut hashes = Vec::with_capacity(total);
+        for (index, file_path) in file_paths.into_iter().enumerate() {
+            let result = match &self.access {
+                AccessType::SecretPhrase(secret) => {
+                    self.client
+                        .tx_mvm_execute(file_path, self.gas_limit, secret)
+                }
+                AccessType::TestAccount(test_account) => {
+                    self.client
+                        .tx_mvm_execute_dev(file_path, self.gas_limit, test_account)
+                }
+            };
+            match result {
+                Ok(hash) => {
+                    on_executed(index, &hash);
+                    hashes.push(hash);
+                }
+                Err(err) => {
+                    let executed = if hashes.is_empty() {
+                        "No transaction was executed.".to_string()
+                    } else {
+                        format!(
+                            "Executed transactions stay committed on chain:\n{}",
+                            hashes
+                                .iter()
+                                .enumerate()
+                                .map(|(index, hash)| format!("    {}: {}", index + 1, hash))
+                                .collect::<Vec<_>>()
+                                .join("\n")
+                        )
+                    };
+                    bail!(
+                        "Transaction {} of {} failed: {}\n{}\n{}\nThe rest of the sequence was not sent.",
+                        index + 1,
+                        total,
+                        file_path,
+                        err,
+                        executed
+                    );
+                }
+            }
+        }
+        Ok(hashes)
+    }
+}
+
+impl PublishSequence {
+    fn file_paths_as_str(&self) -> Result<Vec<&str>> {
+        self.file_paths
+            .iter()
+            .map(|path| {
+                path.to_str()
+                    .ok_or(anyhow!("Error converting path to string"))
+            })
+            .collect()
+    }
+}
+
+/// PublishParamsCmd - Connection parameters
+/// Vec<PathBuf> - The paths to the transaction files (*.mvt)
+impl TryFrom<(&NodeAccessParams, Vec<PathBuf>)> for PublishSequence {
+    type Error = Error;
+
+    fn try_from(
+        value: (&NodeAccessParams, Vec<PathBuf>),
+    ) -> std::result::Result<Self, Self::Error> {
+        let (params, file_paths) = value;
+        ensure!(!file_paths.is_empty(), "The sequence is empty");
+        let (client, node_url, access, gas_limit) = connect(params)?;
+
+        Ok(PublishSequence {
+            client,
+            node_url,
+            file_paths,
+            gas_limit,
+            access,
+        })
+    }
+}
+
+/// Creates the client, the node url and the access type from the connection parameters.
+fn connect(params: &NodeAccessParams) -> Result<(PontemClient, Url, AccessType, u64)> {
+    let gas_limit = match params.gas_limit {
+        Some(GasLimit::Fixed(gas_limit)) => gas_limit,
+        Some(GasLimit::Auto(_)) => {
+            bail!("Automatic gas estimation is only supported for transactions (dove call)")
+        }
+        None => bail!("Please specify gas limit"),
+    };
+    let mut url_to_node = params.url_to_node.clone();
+
+    let access = if params.secret_phrase {
+        // Request secret phrases
+        let secret = cli_entering_a_secret_phrase()?;
+        AccessType::SecretPhrase(secret)
+    } else if let Some(test_account_or_name_key) = &params.account {
+        match cli_name_to_key(test_account_or_name_key)? {
+            Some(WalletKey {
+                secret_phrase,
+                node_address,
+            }) => {
+                url_to_node = node_address;
+                AccessType::SecretPhrase(secret_phrase)
+            }
+            None => AccessType::TestAccount(test_account_or_name_key.to_owned()),
+        }
+    } else {
+        bail!("Specify name of key or name of test account or secret phrase")
+    };
+
+    let client = PontemClient::new(url_to_node.as_str())?;
+    Ok((client, url_to_node, access, gas_limit))
+}
+
+/// Returns the address of the account that signs the transactions.
+fn signer_address(client: &PontemClient, access: &AccessType) -> Result<AccountAddress> {
+    match access {
+        AccessType::SecretPhrase(secret) => ss58_to_address(&client.ss58_address(secret)?),
+        AccessType::TestAccount(name) => dev_account(name)
+            .map(Ok)
+            .unwrap_or_else(|| ss58_to_address(name))
+            .map_err(|_| anyhow!("Unknown test account: {}", name)),
+    }
+}
+
 impl Publish {
     fn file_type(&self) -> Result<FileType> {
         let ext = self
@@ -161,6 +407,43 @@ impl Publish {
         })
     }
 
+    fn publish_package(&self, bundle_path: &Path) -> Result<String> {
+        let bundle_path = bundle_path
+            .to_str()
+            .ok_or(anyhow!("Error converting path to string"))?;
+        match &self.access {
+            AccessType::SecretPhrase(secret) => {
+                self.client
+                    .tx_mvm_publish_package(bundle_path, self.gas_limit, secret)
+            }
+            AccessType::TestAccount(test_account) => {
+                self.client
+                    .tx_mvm_publish_package_dev(bundle_path, self.gas_limit, test_account)
+            }
+        }
+    }
+
+    /// The node accepts only the legacy bundle layout.
+    /// The versioned bundle is converted into a legacy bundle next to it: {name}.legacy.pac
+    /// Returns None if the bundle is already legacy. The caller removes the converted file.
+    fn legacy_bundle(&self) -> Result<Option<PathBuf>> {
+        let bytes = fs::read(&self.file_path)?;
+        if !Bundle::is_versioned(&bytes) {
+            return Ok(None);
+        }
+
+        let name = self
+            .file_path
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let path = self
+            .file_path
+            .with_file_name(format!("{}.legacy.pac", name));
+        fs::write(&path, Bundle::decode(&bytes)?.encode_legacy()?)?;
+        Ok(Some(path))
+    }
+
     fn file_path_as_str(&self) -> Result<&str> {
         self.file_path
             .to_str()
@@ -180,6 +463,18 @@ enum FileType {
     TX,
 }
 
+/// Returns the address of the wallet key.
+/// The password is requested if the key is encrypted.
+pub(crate) fn wallet_key_address(key_name: &str) -> Result<Option<AccountAddress>> {
+    let key = match cli_name_to_key(key_name)? {
+        Some(key) => key,
+        None => return Ok(None),
+    };
+    let address =
+        PontemClient::new(key.node_address.as_str())?.ss58_address(&key.secret_phrase)?;
+    ss58_to_address(&address).map(Some)
+}
+
 /// Checking for a key with this name and getting the content
 fn cli_name_to_key(key_name: &str) -> Result<Option<WalletKey>> {
     // Checking for a saved key with this name
@@ -198,3 +493,22 @@ fn cli_name_to_key(key_name: &str) -> Result<Option<WalletKey>> {
     }
     phrase.map(Some)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::{GasLimit, DEFAULT_GAS_MULTIPLIER};
+
+    #[test]
+    fn test_gas_limit() {
+        assert_eq!(GasLimit::from_str("300").unwrap(), GasLimit::Fixed(300));
+        assert_eq!(
+            GasLimit::from_str("auto").unwrap(),
+            GasLimit::Auto(DEFAULT_GAS_MULTIPLIER)
+        );
+        assert_eq!(GasLimit::from_str("auto:1.5").unwrap(), GasLimit::Auto(1.5));
+        assert!(GasLimit::from_str("auto:0").is_err());
+        assert!(GasLimit::from_str("auto:x").is_err());
+        assert!(GasLimit::from_str("-1").is_err());
+    }
+}
@@ -0,0 +1,515 @@
+use std::str::FromStr;
+
+use anyhow::{Error, Result};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
+use move_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue};
+
+use lang::ss58::address_to_ss58_with_prefix;
+
+/// Output format of `dove view`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Move-like text.
+    Text,
+    /// JSON with the annotated values.
+    Json,
+    /// YAML with the rendered values.
+    Yaml,
+    /// Aligned columns: field path and value.
+    Table,
+    /// Comma-separated field paths and values.
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "text" => OutputFormat::Text,
+            "json" => OutputFormat::Json,
+            "yaml" => OutputFormat::Yaml,
+            "table" => OutputFormat::Table,
+            "csv" => OutputFormat::Csv,
+            _ => bail!(
+                "Unknown output format: {}. Expected text, json, yaml, table or csv",
+                s
+            ),
+        })
+    }
+}
+
+/// Rendering of the addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFormat {
+    /// 0x1
+    Hex,
+    /// SS58 with the network prefix.
+    Ss58(u16),
+}
+
+/// Rendered value of the resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// Empty Option.
+    None,
+    Bool(bool),
+    /// Integer in the decimal notation.
+    Number(String),
+    /// Address, bytes or text.
+    String(String),
+    List(Vec<Value>),
+    Fields(Vec<(String, Value)>),
+}
+
+/// Renders the resource. Well-known structs are rendered by their meaning:
+///     U256 - decimal number
+///     ASCII::String - string
+///     Option<T> - value or none
+/// vector<u8> is rendered as a string if it is printable UTF-8 text and as hex otherwise.
+pub fn render(resource: &AnnotatedMoveStruct, addresses: AddressFormat) -> Value {
+    render_struct(resource, addresses)
+}
+
+fn render_struct(st: &AnnotatedMoveStruct, addresses: AddressFormat) -> Value {
+    if let Some(value) = render_well_known(st, addresses) {
+        return value;
+    }
+    Value::Fields(
+        st.value
+            .iter()
+            .map(|(name, value)| (name.to_string(), render_value(value, addresses)))
+            .collect(),
+    )
+}
+
+fn render_value(value: &AnnotatedMoveValue, addresses: AddressFormat) -> Value {
+    match value {
+        AnnotatedMoveValue::U8(value) => Value::Number(value.to_string()),
+        AnnotatedMoveValue::U64(value) => Value::Number(value.to_string()),
+        AnnotatedMoveValue::U128(value) => Value::Number(value.to_string()),
+        AnnotatedMoveValue::Bool(value) => Value::Bool(*value),
+        AnnotatedMoveValue::Address(address) => Value::String(render_address(address, addresses)),
+        AnnotatedMoveValue::Bytes(bytes) => Value::String(render_bytes(bytes)),
+        AnnotatedMoveValue::Vector(_, values) => Value::List(
+            values
+                .iter()
+                .map(|value| render_value(value, addresses))
+                .collect(),
+        ),
+        AnnotatedMoveValue::Struct(st) => render_struct(st, addresses),
+    }
+}
+
+fn render_well_known(st: &AnnotatedMoveStruct, addresses: AddressFormat) -> Option<Value> {
+    let field = |name: &str| {
+        st.value
+            .iter()
+            .find(|(field, _)| field.as_str() == name)
+            .map(|(_, value)| value)
+    };
+    if is_type(&st.type_, "U256", "U256") && st.value.len() == 4 {
+        let words = ["v0", "v1", "v2", "v3"]
+            .iter()
+            .map(|name| match field(name) {
+                Some(AnnotatedMoveValue::U64(word)) => Some(*word),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        if let Some(words) = words {
+            return Some(Value::Number(words_to_decimal(&words)));
+        }
+    }
+    if is_type(&st.type_, "ASCII", "String") && st.value.len() == 1 {
+        if let Some(AnnotatedMoveValue::Bytes(bytes)) = field("bytes") {
+            if bytes.is_ascii() {
+                return Some(Value::String(String::from_utf8_lossy(bytes).to_string()));
+            }
+        }
+    }
+    if is_type(&st.type_, "Option", "Option") && st.value.len() == 1 {
+        match field("vec") {
+            Some(AnnotatedMoveValue::Vector(_, values)) if values.is_empty() => {
+                return Some(Value::None)
+            }
+            Some(AnnotatedMoveValue::Vector(_, values)) if values.len() == 1 => {
+                return Some(render_value(&values[0], addresses))
+            }
+            Some(AnnotatedMoveValue::Bytes(bytes)) if bytes.is_empty() => {
+                return Some(Value::None)
+            }
+            Some(AnnotatedMoveValue::Bytes(bytes)) if bytes.len() == 1 => {
+                return Some(Value::Number(bytes[0].to_string()))
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Struct of the standard library at 0x1.
+fn is_type(tag: &StructTag, module: &str, name: &str) -> bool {
+    tag.address == CORE_CODE_ADDRESS && tag.module.as_str() == module && tag.name.as_str() == name
+}
+
+fn render_address(address: &AccountAddress, addresses: AddressFormat) -> String {
+    match addresses {
+        AddressFormat::Hex => address.to_hex_literal(),
+        AddressFormat::Ss58(prefix) => address_to_ss58_with_prefix(address, prefix)
+            .unwrap_or_else(|_| address.to_hex_literal()),
+    }
+}
+
+/// Printable UTF-8 text as is, other bytes as hex.
+fn render_bytes(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) if !text.is_empty() && !text.chars().any(char::is_control) => text.to_string(),
+        _ => format!("0x{}", hex::encode(bytes)),
+    }
+}
+
+/// Decimal notation of the number of the 64-bit words, the least significant word first.
+fn words_to_decimal(words: &[u64]) -> String {
+    let mut limbs = words
+        .iter()
+        .rev()
+        .flat_map(|word| [(word >> 32) as u32, *word as u32])
+        .collect::<Vec<_>>();
+    let mut digits = Vec::new();
+    while limbs.iter().any(|limb| *limb != 0) {
+        let mut remainder = 0u64;
+        for limb in limbs.iter_mut() {
+            let value = (remainder << 32) | *limb as u64;
+            *limb = (value / 10) as u32;
+            remainder = value % 10;
+        }
+        digits.push(char::from(b'0' + remainder as u8));
+    }
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.iter().rev().collect()
+}
+
+/// Leaf fields of the rendered value: coin.value, items[0]...
+pub fn flatten(value: &Value) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    flatten_value(String::new(), value, &mut fields);
+    fields
+}
+
+fn flatten_value(path: String, value: &Value, fields: &mut Vec<(String, String)>) {
+    match value {
+        Value::Fields(values) => {
+            for (name, value) in values {
+                let path = if path.is_empty() {
+                    name.to_owned()
+                } else {
+                    format!("{}.{}", path, name)
+                };
+                flatten_value(path, value, fields);
+            }
+        }
+        Value::List(values) if !values.is_empty() => {
+            for (index, value) in values.iter().enumerate() {
+                flatten_value(format!("{}[{}]", path, index), value, fields);
+            }
+        }
+        value => fields.push((path, value_to_string(value))),
+    }
+}
+
+/// The value in one line: 100, [1, 2], {value: 100}
+pub fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::None => "none".to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(value) | Value::String(value) => value.to_owned(),
+        Value::List(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(value_to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Fields(values) => format!(
+            "{{{}}}",
+            values
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value_to_string(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// The field by the path of [flatten]: coin.value, items[0]
+pub fn field<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut value = value;
+    for name in path.split('.') {
+        let mut parts = name.split('[');
+        let name = parts.next()?;
+        value = match value {
+            Value::Fields(values) => values
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value)?,
+            _ => return None,
+        };
+        for index in parts {
+            let index: usize = index.strip_suffix(']')?.parse().ok()?;
+            value = match value {
+                Value::List(values) => values.get(index)?,
+                _ => return None,
+            };
+        }
+    }
+    Some(value)
+}
+
+/// One-line summary of the fields: value: 100, owner: 0x1, coin: {..}, items: [3 items]
+pub fn summary(value: &Value) -> String {
+    match value {
+        Value::Fields(values) => values
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, short_value(value)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        value => short_value(value),
+    }
+}
+
+/// Scalars as is, nested structs and lists folded.
+fn short_value(value: &Value) -> String {
+    match value {
+        Value::Fields(values) if !values.is_empty() => "{..}".to_string(),
+        Value::List(values) if !values.is_empty() => format!("[{} items]", values.len()),
+        value => value_to_string(value),
+    }
+}
+
+/// Aligned columns of the field paths and values.
+pub fn to_table(value: &Value) -> String {
+    let fields = flatten(value);
+    let width = fields
+        .iter()
+        .map(|(path, _)| path.len())
+        .max()
+        .unwrap_or_default()
+        .max("FIELD".len());
+    let mut table = format!("{:<width$}  VALUE", "FIELD", width = width);
+    for (path, value) in fields {
+        table.push_str(&format!("\n{:<width$}  {}", path, value, width = width));
+    }
+    table
+}
+
+/// CSV with the header: field,value
+pub fn to_csv(value: &Value) -> String {
+    let mut csv = "field,value".to_string();
+    for (path, value) in flatten(value) {
+        csv.push_str(&format!("\n{},{}", csv_escape(&path), csv_escape(&value)));
+    }
+    csv
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// YAML document of the resource.
+pub fn to_yaml(tag: &StructTag, height: &str, value: &Value) -> String {
+    let mut yaml = format!(
+        "type: {}\nheight: {}\nvalue:",
+        yaml_string(&tag.to_string()),
+        yaml_string(height)
+    );
+    write_yaml(value, 1, &mut yaml);
+    yaml
+}
+
+/// Appends the value to the YAML: scalars after the key, collections on the next lines.
+fn write_yaml(value: &Value, indent: usize, yaml: &mut String) {
+    let padding = "  ".repeat(indent);
+    match value {
+        Value::Fields(values) if !values.is_empty() => {
+            for (name, value) in values {
+                yaml.push_str(&format!("\n{}{}:", padding, name));
+                write_yaml(value, indent + 1, yaml);
+            }
+        }
+        Value::List(values) if !values.is_empty() => {
+            for value in values {
+                yaml.push_str(&format!("\n{}-", padding));
+                write_yaml(value, indent + 1, yaml);
+            }
+        }
+        Value::Fields(_) => yaml.push_str(" {}"),
+        Value::List(_) => yaml.push_str(" []"),
+        Value::None => yaml.push_str(" null"),
+        Value::Bool(value) => yaml.push_str(&format!(" {}", value)),
+        Value::Number(value) => yaml.push_str(&format!(" {}", value)),
+        Value::String(value) => yaml.push_str(&format!(" {}", yaml_string(value))),
+    }
+}
+
+/// Double-quoted YAML string.
+fn yaml_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            ch if ch.is_control() => quoted.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use move_binary_format::file_format::AbilitySet;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::StructTag;
+    use move_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue};
+
+    use super::{
+        field, render, render_bytes, summary, to_csv, to_table, value_to_string,
+        words_to_decimal, AddressFormat, Value,
+    };
+
+    #[test]
+    fn test_words_to_decimal() {
+        assert_eq!(words_to_decimal(&[0, 0, 0, 0]), "0");
+        assert_eq!(words_to_decimal(&[100, 0, 0, 0]), "100");
+        assert_eq!(words_to_decimal(&[0, 1, 0, 0]), "18446744073709551616");
+        assert_eq!(
+            words_to_decimal(&[u64::MAX, u64::MAX, u64::MAX, u64::MAX]),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+    }
+
+    #[test]
+    fn test_render_bytes() {
+        assert_eq!(render_bytes(b"PONT"), "PONT");
+        assert_eq!(render_bytes(&[0, 1, 2]), "0x000102");
+        assert_eq!(render_bytes(&[]), "0x");
+    }
+
+    #[test]
+    fn test_flat_outputs() {
+        let value = Value::Fields(vec![
+            (
+                "coin".to_string(),
+                Value::Fields(vec![(
+                    "value".to_string(),
+                    Value::Number("100".to_string()),
+                )]),
+            ),
+            ("name".to_string(), Value::String("a,b".to_string())),
+            ("lock".to_string(), Value::None),
+        ]);
+        assert_eq!(
+            to_table(&value),
+            "FIELD       VALUE\ncoin.value  100\nname        a,b\nlock        none"
+        );
+        assert_eq!(
+            to_csv(&value),
+            "field,value\ncoin.value,100\nname,\"a,b\"\nlock,none"
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let value = Value::Fields(vec![
+            ("value".to_string(), Value::Number("100".to_string())),
+            ("owner".to_string(), Value::String("0x1".to_string())),
+            (
+                "coin".to_string(),
+                Value::Fields(vec![("value".to_string(), Value::Bool(true))]),
+            ),
+            (
+                "items".to_string(),
+                Value::List(vec![Value::None, Value::None, Value::None]),
+            ),
+            ("empty".to_string(), Value::List(vec![])),
+        ]);
+        assert_eq!(
+            summary(&value),
+            "value: 100, owner: 0x1, coin: {..}, items: [3 items], empty: []"
+        );
+    }
+
+    #[test]
+    fn test_field() {
+        let value = Value::Fields(vec![
+            (
+                "coin".to_string(),
+                Value::Fields(vec![(
+                    "value".to_string(),
+                    Value::Number("100".to_string()),
+                )]),
+            ),
+            (
+                "items".to_string(),
+                Value::List(vec![
+                    Value::Number("1".to_string()),
+                    Value::String("a".to_string()),
+                ]),
+            ),
+        ]);
+        let field = |path| field(&value, path).map(value_to_string);
+        assert_eq!(field("coin.value").as_deref(), Some("100"));
+        assert_eq!(field("coin").as_deref(), Some("{value: 100}"));
+        assert_eq!(field("items").as_deref(), Some("[1, a]"));
+        assert_eq!(field("items[1]").as_deref(), Some("a"));
+        assert_eq!(field("items[2]"), None);
+        assert_eq!(field("coin.amount"), None);
+        assert_eq!(field("coin.value.inner"), None);
+    }
+
+    #[test]
+    fn test_well_known_address() {
+        let u256 = |address| AnnotatedMoveStruct {
+            abilities: AbilitySet::EMPTY,
+            type_: StructTag {
+                address,
+                module: Identifier::new("U256").unwrap(),
+                name: Identifier::new("U256").unwrap(),
+                type_params: vec![],
+            },
+            value: ["v0", "v1", "v2", "v3"]
+                .iter()
+                .map(|name| (Identifier::new(*name).unwrap(), AnnotatedMoveValue::U64(1)))
+                .collect(),
+        };
+        assert_eq!(
+            render(
+                &u256(AccountAddress::from_hex_literal("0x1").unwrap()),
+                AddressFormat::Hex
+            ),
+            Value::Number(
+                "6277101735386680764176071790128604879584176795969512275969".to_string()
+            )
+        );
+        assert!(matches!(
+            render(
+                &u256(AccountAddress::from_hex_literal("0x2").unwrap()),
+                AddressFormat::Hex
+            ),
+            Value::Fields(_)
+        ));
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+
+/// Change of the resource field between two snapshots.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "change", rename_all = "lowercase")]
+pub enum Change {
+    /// The field appeared.
+    Added { path: String, value: String },
+    /// The field disappeared.
+    Removed { path: String, value: String },
+    /// The value of the field changed.
+    Changed {
+        path: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Added { path, value } => write!(f, "+ {}: {}", path, value),
+            Change::Removed { path, value } => write!(f, "- {}: {}", path, value),
+            Change::Changed { path, old, new } => write!(f, "{}: {} -> {}", path, old, new),
+        }
+    }
+}
+
+/// Changes between the snapshots flattened by [crate::render::flatten]. An absent resource is an empty snapshot.
+pub fn diff(old: &[(String, String)], new: &[(String, String)]) -> Vec<Change> {
+    let find = |fields: &[(String, String)], path: &str| {
+        fields
+            .iter()
+            .find(|(field, _)| field == path)
+            .map(|(_, value)| value.to_owned())
+    };
+
+    let mut changes = Vec::new();
+    for (path, value) in new {
+        match find(old, path) {
+            None => changes.push(Change::Added {
+                path: path.to_owned(),
+                value: value.to_owned(),
+            }),
+            Some(old) if &old != value => changes.push(Change::Changed {
+                path: path.to_owned(),
+                old,
+                new: value.to_owned(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (path, value) in old {
+        if find(new, path).is_none() {
+            changes.push(Change::Removed {
+                path: path.to_owned(),
+                value: value.to_owned(),
+            });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, Change};
+
+    fn fields(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+        fields
+            .iter()
+            .map(|(path, value)| (path.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = fields(&[("coin.value", "100"), ("items[0]", "1"), ("items[1]", "2")]);
+        let new = fields(&[("coin.value", "110"), ("items[0]", "1"), ("owner", "0x1")]);
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                Change::Changed {
+                    path: "coin.value".to_string(),
+                    old: "100".to_string(),
+                    new: "110".to_string(),
+                },
+                Change::Added {
+                    path: "owner".to_string(),
+                    value: "0x1".to_string(),
+                },
+                Change::Removed {
+                    path: "items[1]".to_string(),
+                    value: "2".to_string(),
+                },
+            ]
+        );
+        assert!(diff(&old, &old).is_empty());
+        assert_eq!(diff(&[], &old).len(), 3);
+    }
+
+    #[test]
+    fn test_change_display() {
+        let change = Change::Changed {
+            path: "coin.value".to_string(),
+            old: "100".to_string(),
+            new: "110".to_string(),
+        };
+        assert_eq!(change.to_string(), "coin.value: 100 -> 110");
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use move_cli::DEFAULT_STORAGE_DIR;
+use move_cli::sandbox::utils::on_disk_state_view::OnDiskStateView;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
+use move_core_types::resolver::{ModuleResolver, ResourceResolver};
+use move_package::compilation::package_layout::CompiledPackageLayout;
+use net::{Block, BytesForBlock, Net};
+use lang::address::AddressResolver;
+
+use crate::call::simulate::project_modules;
+use crate::cmd::view::parse_query;
+use crate::context::Context;
+
+/// Directory of the account resources in the sandbox storage.
+const RESOURCES_DIR: &str = "resources";
+/// Extension of the resource files in the sandbox storage.
+const RESOURCE_EXTENSION: &str = "bcs";
+
+/// Local sandbox storage used by `dove run`.
+///     ./storage
+/// Modules of the project are used if the module is not published to the storage.
+/// The storage has no blocks: the height is ignored.
+pub struct SandboxNet {
+    storage_dir: PathBuf,
+    storage: OnDiskStateView,
+    modules: HashMap<ModuleId, Option<Vec<u8>>>,
+    resolver: AddressResolver,
+}
+
+impl SandboxNet {
+    /// Opens the sandbox storage of the project.
+    pub fn new(ctx: &Context) -> Result<SandboxNet> {
+        let storage_dir = ctx.project_root_dir.join(DEFAULT_STORAGE_DIR);
+        ensure!(
+            storage_dir.exists(),
+            "Sandbox storage {:?} not found. Execute a script with `dove run` first",
+            storage_dir
+        );
+        let storage = OnDiskStateView::create(
+            ctx.path_for_build(None, CompiledPackageLayout::Root),
+            storage_dir.clone(),
+        )?;
+        Ok(SandboxNet {
+            storage_dir,
+            storage,
+            modules: project_modules(ctx).unwrap_or_default(),
+            resolver: ctx.address_resolver(),
+        })
+    }
+
+    /// Returns all resources stored under the account.
+    pub fn list_resources(
+        &self,
+        address: &AccountAddress,
+        height: &Option<Block>,
+    ) -> Result<Vec<(StructTag, BytesForBlock)>> {
+        self.resource_files(address)?
+            .iter()
+            .map(|path| {
+                let tag = self.struct_tag(path)?;
+                let bytes = fs::read(path)?;
+                Ok((
+                    tag,
+                    BytesForBlock(bytes, height.clone().unwrap_or_default()),
+                ))
+            })
+            .collect()
+    }
+
+    /// Resource files of the account: ./storage/0x{ADDRESS}/resources/{STRUCT_TAG}.bcs
+    fn resource_files(&self, address: &AccountAddress) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for account_dir in fs::read_dir(&self.storage_dir)? {
+            let account_dir = account_dir?.path();
+            let is_account = account_dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| AccountAddress::from_hex_literal(name).ok())
+                .map(|account| &account == address)
+                .unwrap_or(false);
+            let resources_dir = account_dir.join(RESOURCES_DIR);
+            if !is_account || !resources_dir.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(resources_dir)? {
+                let file = file?.path();
+                if file.extension().and_then(|ext| ext.to_str()) == Some(RESOURCE_EXTENSION) {
+                    files.push(file);
+                }
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// Struct tag of the resource file.
+    fn struct_tag(&self, path: &Path) -> Result<StructTag> {
+        let name = path
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match parse_query(&self.resolver, &name)? {
+            TypeTag::Struct(tag) => Ok(tag),
+            tp => bail!("Unexpected resource type {} in {:?}", tp, path),
+        }
+    }
+}
+
+impl Net for SandboxNet {
+    fn get_module(
+        &self,
+        module_id: &ModuleId,
+        height: &Option<Block>,
+    ) -> Result<Option<BytesForBlock>> {
+        let bytecode = match self.storage.get_module(module_id)? {
+            Some(bytecode) => Some(bytecode),
+            None => self.modules.get(module_id).cloned().flatten(),
+        };
+        Ok(bytecode.map(|bytecode| BytesForBlock(bytecode, height.clone().unwrap_or_default())))
+    }
+
+    fn get_resource(
+        &self,
+        address: &AccountAddress,
+        tag: &StructTag,
+        height: &Option<Block>,
+    ) -> Result<Option<BytesForBlock>> {
+        Ok(self
+            .storage
+            .get_resource(address, tag)?
+            .map(|bytes| BytesForBlock(bytes, height.clone().unwrap_or_default())))
+    }
+}
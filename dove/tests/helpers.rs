@@ -3,6 +3,9 @@
 use std::path::{PathBuf, Path};
 use std::fs;
 use std::fs::{remove_dir_all, create_dir};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
 use anyhow::{Result, ensure};
 
 /// get tmp_folder, project_folder and remove project folder if exist
@@ -32,6 +35,7 @@ pub fn dove(args: &[&str], project_path: &Path) -> Result<String> {
 
     let output = std::process::Command::new(env!("CARGO_BIN_EXE_dove"))
         .current_dir(project_path)
+        .env("MOVE_HOME", move_home(project_path))
         .args(args)
         .output()?;
 
@@ -47,6 +51,12 @@ pub fn dove(args: &[&str], project_path: &Path) -> Result<String> {
     Ok(String::from_utf8(output.stdout)?)
 }
 
+/// MOVE_HOME of the commands run in the project: wallet keys and the module cache.
+/// Removed with the project.
+pub fn move_home(project_path: &Path) -> PathBuf {
+    project_path.join(".move")
+}
+
 /// Get the project name from "Move.toml"
 pub fn get_project_name_from_toml(project_path: &Path) -> Option<String> {
     let move_toml_path = project_path.join("Move.toml");
@@ -105,6 +115,59 @@ pub fn build(project_dir: &Path) -> Result<String> {
     dove(&["build"], project_dir)
 }
 
+/// Start a local JSON-RPC stand-in for the node. Returns the url of the node.
+/// The handler receives the method and the params and returns the result.
+pub fn start_node<F>(handler: F) -> String
+where
+    F: Fn(&str, &serde_json::Value) -> serde_json::Value + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+                    break;
+                }
+                let line = line.to_lowercase();
+                if let Some(length) = line.strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap_or(0);
+                }
+            }
+            let mut body = vec![0; content_length];
+            if reader.read_exact(&mut body).is_err() {
+                continue;
+            }
+            let request: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+            let result = handler(
+                request["method"].as_str().unwrap_or_default(),
+                &request["params"],
+            );
+            let response = serde_json::json!({
+                "id": request["id"],
+                "jsonrpc": "2.0",
+                "result": result,
+            })
+            .to_string();
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.len(),
+                response
+            );
+        }
+    });
+    url
+}
+
 fn copy_folder(from: &Path, to: &Path) -> Result<()> {
     for path in fs::read_dir(from)?
         .filter_map(|path| path.ok())
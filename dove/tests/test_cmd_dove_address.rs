@@ -0,0 +1,61 @@
+mod helpers;
+
+use helpers::{new_demo_project, dove, delete_project};
+
+const ALICE_HEX: &str = "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+const ALICE_SS58: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+const ALICE_PONTEM: &str = "gkQ5K6EnLRgZkwozG8GiBAEnJyM6FxzbSaSmVhKJ2w8FcK7ih";
+
+/// $ dove address convert 0xd435... --prefix pontem
+/// $ dove address convert //Alice
+/// $ dove address convert gkQ5... --hex
+/// $ dove address info Demo
+/// $ dove address dev
+#[test]
+fn test_cmd_dove_address() {
+    let project_name = "project_address";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    for (args, expected) in [
+        (
+            vec!["address", "convert", ALICE_HEX, "--prefix", "pontem"],
+            ALICE_PONTEM,
+        ),
+        (
+            vec!["address", "convert", ALICE_HEX, "--prefix", "105"],
+            ALICE_PONTEM,
+        ),
+        (vec!["address", "convert", "//Alice"], ALICE_SS58),
+        (vec!["address", "convert", ALICE_PONTEM], ALICE_HEX),
+        (
+            vec!["address", "convert", ALICE_PONTEM, "--prefix", "42"],
+            ALICE_SS58,
+        ),
+        (vec!["address", "convert", ALICE_SS58, "--hex"], ALICE_HEX),
+    ] {
+        let output = dove(&args, &project_folder).unwrap();
+        assert_eq!(output.trim(), expected, "{:?}", args);
+    }
+
+    let output = dove(&["address", "info", "Demo"], &project_folder).unwrap();
+    assert!(output.contains("Hex: 0x2"));
+    assert!(output.contains("Address aliases: Demo"));
+
+    let output = dove(&["address", "info", ALICE_PONTEM], &project_folder).unwrap();
+    assert!(output.contains(&format!("SS58 (105): {}", ALICE_PONTEM)));
+    assert!(output.contains("Dev account: alice"));
+
+    let output = dove(&["address", "dev"], &project_folder).unwrap();
+    assert!(output.contains(ALICE_HEX));
+    assert!(output.contains(ALICE_SS58));
+
+    for prefix in ["46", "16384", "unknown"] {
+        assert!(dove(
+            &["address", "convert", ALICE_HEX, "--prefix", prefix],
+            &project_folder
+        )
+        .is_err());
+    }
+
+    delete_project(&project_folder).unwrap();
+}
@@ -1,6 +1,7 @@
 mod helpers;
 
-use helpers::{new_demo_project, dove, delete_project};
+use std::fs;
+use helpers::{new_demo_project, dove, delete_project, move_home, start_node};
 
 /// $ dove call 'main()'
 /// $ dove call 'one_param(true)'
@@ -62,3 +63,247 @@ fn test_cmd_dove_call_with_type() {
 
     delete_project(&project_folder).unwrap();
 }
+
+/// Arguments from a file
+/// $ dove call 'two_params' --args-file args.json
+/// $ dove call 'two_params' --args-file args.toml
+/// $ dove call 'two_params' --args-file named_args.toml
+/// $ dove call 'with_type' --args-file args.json --type-args-file types.json
+#[test]
+fn test_cmd_dove_call_with_args_file() {
+    let project_name = "project_call_with_args_file";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    fs::write(project_folder.join("args.json"), "[1, 1]").unwrap();
+    fs::write(project_folder.join("args.toml"), "args = [1, 1]").unwrap();
+    fs::write(
+        project_folder.join("named_args.toml"),
+        "[args]\nb = 1\na = 1",
+    )
+    .unwrap();
+    fs::write(project_folder.join("one_arg.json"), "[1]").unwrap();
+    fs::write(project_folder.join("types.json"), r#"["u8"]"#).unwrap();
+
+    for call in [
+        vec!["call", "two_params", "--args-file", "args.json"],
+        vec!["call", "two_params", "--args-file", "args.toml"],
+        vec!["call", "two_params", "--args-file", "named_args.toml"],
+        vec![
+            "call",
+            "with_type",
+            "--args-file",
+            "one_arg.json",
+            "--type-args-file",
+            "types.json",
+        ],
+    ] {
+        dove(&call, &project_folder).unwrap();
+    }
+
+    delete_project(&project_folder).unwrap();
+}
+
+/// Named arguments
+/// $ dove call 'two_params(b: 1, a: 1)'
+/// $ dove call 'with_type<u8>(_a: 1)'
+#[test]
+fn test_cmd_dove_call_with_named_args() {
+    let project_name = "project_call_with_named_args";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    for call in ["two_params(b: 1, a: 1)", "with_type<u8>(_a: 1)"] {
+        dove(&["call", call], &project_folder).unwrap();
+    }
+    assert!(dove(&["call", "two_params(a: 1, c: 1)"], &project_folder).is_err());
+    assert!(dove(&["call", "two_params(a: 1)"], &project_folder).is_err());
+
+    delete_project(&project_folder).unwrap();
+}
+
+/// Named arguments of a dependency function
+/// $ dove call 'Lib::check(b: 1, a: 1)'
+#[test]
+fn test_cmd_dove_call_dependency_with_named_args() {
+    let project_name = "project_call_dependency_with_named_args";
+    let project_folder = new_demo_project(project_name).unwrap();
+    let lib_path = project_folder.join("lib");
+    fs::create_dir_all(lib_path.join("sources")).unwrap();
+    fs::write(
+        lib_path.join("Move.toml"),
+        "[package]\nname = \"DemoLib\"\nversion = \"0.1.0\"\n\n[addresses]\nDemo = \"0x2\"\n",
+    )
+    .unwrap();
+    fs::write(
+        lib_path.join("sources").join("lib.move"),
+        "module Demo::Lib {
+            public(script) fun check(a: u8, b: u8) { assert!(a == b, 1); }
+        }",
+    )
+    .unwrap();
+    let mut move_toml = fs::read_to_string(project_folder.join("Move.toml")).unwrap();
+    move_toml.push_str("\nDemoLib = { local = \"./lib\" }\n");
+    fs::write(project_folder.join("Move.toml"), move_toml).unwrap();
+
+    dove(&["call", "Lib::check(b: 1, a: 1)"], &project_folder).unwrap();
+    let tx_path = project_folder
+        .join("build")
+        .join("for_tests")
+        .join("transaction")
+        .join("check.mvt");
+    assert!(tx_path.exists());
+
+    let err = dove(&["call", "Lib::check(a: 1, c: 1)"], &project_folder)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Unknown parameter 'c'"));
+
+    delete_project(&project_folder).unwrap();
+}
+
+/// Unresolved call
+/// $ dove call 'two_param(1, 1)'
+/// $ dove call 'with_type(1)'
+#[test]
+fn test_cmd_dove_call_suggestions() {
+    let project_name = "project_call_suggestions";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    let err = dove(&["call", "two_param(1, 1)"], &project_folder)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Did you mean:"));
+    assert!(err.contains("two_params(a: u8, b: u8) [for_tests]"));
+
+    let err = dove(&["call", "with_type(1)"], &project_folder)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Rejected candidates:"));
+    assert!(err.contains("with_type<T0>(_a: u8) [for_tests]"));
+    assert!(err.contains("Expected 1 type arguments, but 0 were passed."));
+
+    delete_project(&project_folder).unwrap();
+}
+
+/// Simulation
+/// $ dove call 'two_params(1, 1)' --simulate --sender //Alice
+/// $ dove call 'two_params(1, 2)' --simulate --sender //Alice
+#[test]
+fn test_cmd_dove_call_simulate() {
+    let project_name = "project_call_simulate";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    let output = dove(
+        &[
+            "call",
+            "two_params(1, 1)",
+            "--simulate",
+            "--sender",
+            "//Alice",
+        ],
+        &project_folder,
+    )
+    .unwrap();
+    assert!(output.contains("Status: executed"));
+    assert!(output.contains("Gas used:"));
+
+    let output = dove(
+        &[
+            "call",
+            "two_params(1, 2)",
+            "--simulate",
+            "--sender",
+            "//Alice",
+        ],
+        &project_folder,
+    )
+    .unwrap();
+    assert!(output.contains("Status: failed"));
+
+    delete_project(&project_folder).unwrap();
+}
+
+/// Simulation on top of the node state without the module cache
+/// $ dove call 'two_params(1, 1)' --simulate --sender //Alice --fork http://127.0.0.1:9933 --no-cache
+#[test]
+fn test_cmd_dove_call_simulate_fork_no_cache() {
+    let project_name = "project_call_simulate_fork_no_cache";
+    let project_folder = new_demo_project(project_name).unwrap();
+    let api = start_node(|_, _| serde_json::Value::Null);
+
+    let output = dove(
+        &[
+            "call",
+            "two_params(1, 1)",
+            "--simulate",
+            "--sender",
+            "//Alice",
+            "--fork",
+            &api,
+            "--no-cache",
+        ],
+        &project_folder,
+    )
+    .unwrap();
+    assert!(output.contains("Status: executed"));
+    assert!(!move_home(&project_folder)
+        .join("cache")
+        .join("modules")
+        .exists());
+
+    // The cache applies to the fork only.
+    dove(
+        &["call", "two_params(1, 1)", "--simulate", "--no-cache"],
+        &project_folder,
+    )
+    .unwrap_err();
+
+    delete_project(&project_folder).unwrap();
+}
+
+/// Call sequence
+/// $ dove call --sequence calls.toml
+/// $ dove call --sequence calls.toml --simulate --sender //Alice
+#[test]
+fn test_cmd_dove_call_sequence() {
+    let project_name = "project_call_sequence";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    fs::write(
+        project_folder.join("calls.toml"),
+        r#"
+            [[call]]
+            call = "main()"
+
+            [[call]]
+            call = "two_params"
+            args = { a = 1, b = 1 }
+        "#,
+    )
+    .unwrap();
+
+    dove(&["call", "--sequence", "calls.toml"], &project_folder).unwrap();
+    let tx_dir = project_folder
+        .join("build")
+        .join("for_tests")
+        .join("transaction");
+    assert!(tx_dir.join("calls_1_main.mvt").exists());
+    assert!(tx_dir.join("calls_2_two_params.mvt").exists());
+
+    let output = dove(
+        &[
+            "call",
+            "--sequence",
+            "calls.toml",
+            "--simulate",
+            "--sender",
+            "//Alice",
+        ],
+        &project_folder,
+    )
+    .unwrap();
+    assert!(output.contains("Call main:"));
+    assert!(output.contains("Call two_params:"));
+    assert!(dove(&["call", "main()", "--non-atomic"], &project_folder).is_err());
+
+    delete_project(&project_folder).unwrap();
+}
@@ -0,0 +1,242 @@
+mod helpers;
+
+use std::fs;
+use std::path::Path;
+use move_core_types::account_address::AccountAddress;
+use helpers::{new_demo_project, dove, delete_project, start_node};
+
+/// $ dove check-compat PATH/TO/BUNDLE.pac
+#[test]
+fn test_cmd_dove_check_compat() {
+    let project_name = "project_check_compat";
+    let project_path = new_demo_project(project_name).unwrap();
+    let store_path = project_path.join("sources").join("store.move");
+    let baseline_path = std::env::temp_dir().join("project_check_compat_baseline.pac");
+
+    fs::write(
+        &store_path,
+        "module Demo::Store {
+            struct Counter has key { value: u64 }
+            public fun value(): u64 { 1 }
+        }",
+    )
+    .unwrap();
+    dove(&["deploy"], &project_path).unwrap();
+    fs::copy(
+        project_path
+            .join("build")
+            .join("for_tests")
+            .join("bundles")
+            .join("for_tests.pac"),
+        &baseline_path,
+    )
+    .unwrap();
+    let baseline = baseline_path.to_string_lossy().to_string();
+
+    let output = dove(&["check-compat", &baseline], &project_path).unwrap();
+    assert!(output.contains("unchanged"));
+    assert!(output.contains("All changes are compatible"));
+
+    // New functions are compatible.
+    fs::write(
+        &store_path,
+        "module Demo::Store {
+            struct Counter has key { value: u64 }
+            public fun value(): u64 { 1 }
+            public fun value_2(): u64 { 2 }
+        }",
+    )
+    .unwrap();
+    let output = dove(&["check-compat", &baseline], &project_path).unwrap();
+    assert!(output.contains("    changed      0x2::Store\n"));
+    assert!(output.contains("All changes are compatible"));
+
+    // Changed signature and struct layout are not.
+    fs::write(
+        &store_path,
+        "module Demo::Store {
+            struct Counter has key { value: u128 }
+            public fun value(v: u64): u64 { v }
+        }",
+    )
+    .unwrap();
+    let err = dove(&["check-compat", &baseline], &project_path)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("0x2::Store"));
+    assert!(err.contains("function value"));
+    assert!(err.contains("struct Counter"));
+
+    fs::remove_file(&baseline_path).unwrap();
+    delete_project(&project_path).unwrap();
+}
+
+/// The baseline is the build directory of the previous version.
+/// Only the package modules are compared: the scripts and the dependency modules are skipped.
+/// $ dove check-compat PATH/TO/PREVIOUS/BUILD
+/// $ dove check-compat PATH/TO/PREVIOUS/BUILD/for_tests
+#[test]
+fn test_cmd_dove_check_compat_build_dir() {
+    let project_name = "project_check_compat_build_dir";
+    let project_path = new_demo_project(project_name).unwrap();
+    let store_path = project_path.join("sources").join("store.move");
+    let baseline_path = std::env::temp_dir().join("project_check_compat_baseline_build");
+    let lib_path = project_path.join("lib");
+    fs::create_dir_all(lib_path.join("sources")).unwrap();
+    fs::write(
+        lib_path.join("Move.toml"),
+        "[package]\nname = \"DemoLib\"\nversion = \"0.1.0\"\n\n[addresses]\nDemo = \"0x2\"\n",
+    )
+    .unwrap();
+    fs::write(
+        lib_path.join("sources").join("lib.move"),
+        "module Demo::Lib {
+            public fun value(): u64 { 1 }
+        }",
+    )
+    .unwrap();
+    let mut move_toml = fs::read_to_string(project_path.join("Move.toml")).unwrap();
+    move_toml.push_str("\nDemoLib = { local = \"./lib\" }\n");
+    fs::write(project_path.join("Move.toml"), move_toml).unwrap();
+    fs::write(
+        &store_path,
+        "module Demo::Store {
+            struct Counter has key { value: u64 }
+            public fun value(): u64 { 1 }
+        }",
+    )
+    .unwrap();
+
+    dove(&["build"], &project_path).unwrap();
+    if baseline_path.exists() {
+        fs::remove_dir_all(&baseline_path).unwrap();
+    }
+    copy_dir(&project_path.join("build"), &baseline_path);
+    let package_baseline = baseline_path.join("for_tests");
+    assert!(package_baseline
+        .join("bytecode_modules")
+        .join("dependencies")
+        .join("DemoLib")
+        .join("Lib.mv")
+        .exists());
+    assert!(package_baseline.join("bytecode_scripts").exists());
+
+    for baseline in [&baseline_path, &package_baseline] {
+        let baseline = baseline.to_string_lossy().to_string();
+        let output = dove(&["check-compat", &baseline], &project_path).unwrap();
+        assert!(output.contains("    unchanged    0x2::Store\n"));
+        assert!(!output.contains("removed"));
+        assert!(output.contains("All changes are compatible"));
+    }
+
+    // The module removed from the package is still reported.
+    fs::remove_file(&store_path).unwrap();
+    let err = dove(
+        &["check-compat", &package_baseline.to_string_lossy()],
+        &project_path,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("removed      0x2::Store"));
+    assert!(!err.contains("0x2::Lib"));
+
+    fs::remove_dir_all(&baseline_path).unwrap();
+    delete_project(&project_path).unwrap();
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let path = entry.unwrap().path();
+        let target = to.join(path.file_name().unwrap());
+        if path.is_dir() {
+            copy_dir(&path, &target);
+        } else {
+            fs::copy(&path, &target).unwrap();
+        }
+    }
+}
+
+/// $ dove check-compat --api http://127.0.0.1:9933 --address 0x3
+#[test]
+fn test_cmd_dove_check_compat_api() {
+    let project_name = "project_check_compat_api";
+    let project_path = new_demo_project(project_name).unwrap();
+    let move_toml_path = project_path.join("Move.toml");
+    let move_toml = fs::read_to_string(&move_toml_path).unwrap();
+    fs::write(
+        project_path.join("sources").join("store.move"),
+        "module Demo::Store {
+            struct Counter has key { value: u64 }
+            public fun value(counter: &Counter): u64 { counter.value }
+        }",
+    )
+    .unwrap();
+
+    // The same module deployed to 0x3.
+    fs::write(
+        &move_toml_path,
+        move_toml.replace("Demo = \"0x2\"", "Demo = \"0x3\""),
+    )
+    .unwrap();
+    dove(&["build"], &project_path).unwrap();
+    let deployed_store = fs::read(
+        project_path
+            .join("build")
+            .join("for_tests")
+            .join("bytecode_modules")
+            .join("Store.mv"),
+    )
+    .unwrap();
+    fs::write(&move_toml_path, move_toml).unwrap();
+
+    // 0x3::Old was deployed from the project and is no longer built.
+    fs::write(
+        project_path.join("deployments.toml"),
+        "[[deployment]]
+kind = \"bundle\"
+name = \"for_tests\"
+network = \"ws://localhost:9944\"
+signer = \"0x3\"
+hash = \"00\"
+tx_hash = \"0x01\"
+timestamp = \"2021-12-01T10:00:00Z\"
+dove_version = \"1.6.0\"
+
+[[deployment.modules]]
+id = \"0x3::Old\"
+hash = \"00\"
+",
+    )
+    .unwrap();
+
+    let account = format!(
+        "0x{}",
+        hex::encode(AccountAddress::from_hex_literal("0x3").unwrap())
+    );
+    let api = start_node(move |method, params| {
+        let module_id = params[0].as_str().unwrap_or_default();
+        if method != "mvm_getModule" || !module_id.starts_with(&account) {
+            serde_json::Value::Null
+        } else if module_id.ends_with(&hex::encode("Store")) {
+            format!("0x{}", hex::encode(&deployed_store)).into()
+        } else if module_id.ends_with(&hex::encode("Old")) {
+            "0x00".into()
+        } else {
+            serde_json::Value::Null
+        }
+    });
+
+    let err = dove(
+        &["check-compat", "--api", &api, "--address", "0x3"],
+        &project_path,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("    unchanged    0x2::Store\n"));
+    assert!(err.contains("    new          0x2::Demo1v\n"));
+    assert!(err.contains("    removed      0x3::Old\n"));
+    assert!(err.contains("Incompatible changes in modules: 0x3::Old"));
+
+    delete_project(&project_path).unwrap();
+}
@@ -2,7 +2,11 @@ mod helpers;
 
 use std::fs;
 use std::io::Read;
-use helpers::{delete_project, dove, new_demo_project};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use dove::bundle::Bundle;
+use helpers::{delete_project, dove, new_demo_project, start_node};
 
 /// Build a project and package
 /// $ dove deploy --modules_exclude Demo1v Demo2v
@@ -37,6 +41,209 @@ fn test_cmd_dove_deploy() {
     delete_project(&project_path).unwrap();
 }
 
+/// The bundle carries the package metadata unless the legacy format is requested
+/// $ dove deploy
+/// $ dove deploy --legacy
+#[test]
+fn test_cmd_dove_deploy_format() {
+    let project_name = "project_deploy_format";
+    let project_path = new_demo_project(project_name).unwrap();
+    let bundle_path = project_path
+        .join("build")
+        .join("for_tests")
+        .join("bundles")
+        .join("for_tests.pac");
+
+    dove(&["deploy"], &project_path).unwrap();
+    let content = fs::read(&bundle_path).unwrap();
+    assert!(content.starts_with(b"DPAC"));
+    assert!(find_u8(&content, b"for_tests"));
+    assert!(find_u8(&content, b"0.1.0"));
+    assert!(find_u8(&content, b"0x2::Demo3v"));
+
+    dove(&["deploy", "--legacy"], &project_path).unwrap();
+    let legacy = fs::read(&bundle_path).unwrap();
+    assert!(!legacy.starts_with(b"DPAC"));
+    assert!(!find_u8(&legacy, b"0x2::Demo3v"));
+    assert!(find_u8(&legacy, b"Demo3v"));
+
+    delete_project(&project_path).unwrap();
+}
+
+/// Included modules pull in their dependencies
+/// $ dove deploy --modules StoreUser
+/// $ dove deploy --modules StoreUser --modules_exclude Store
+/// $ dove deploy --modules_exclude Demo*
+#[test]
+fn test_cmd_dove_deploy_modules() {
+    let project_name = "project_deploy_modules";
+    let project_path = new_demo_project(project_name).unwrap();
+    fs::write(
+        project_path.join("sources").join("store.move"),
+        "module Demo::Store {
+            public fun value(): u64 { 1 }
+        }
+        module Demo::StoreUser {
+            use Demo::Store;
+            public fun run(): u64 { Store::value() }
+        }",
+    )
+    .unwrap();
+    let bundle_path = project_path
+        .join("build")
+        .join("for_tests")
+        .join("bundles")
+        .join("for_tests.pac");
+
+    let output = dove(&["deploy", "--modules", "StoreUser"], &project_path).unwrap();
+    assert!(output.contains("Including '0x2::Store': required by '0x2::StoreUser'"));
+    assert_eq!(
+        bundle_modules(&bundle_path),
+        vec!["0x2::Store", "0x2::StoreUser"]
+    );
+
+    let output = dove(
+        &[
+            "deploy",
+            "--modules",
+            "StoreUser",
+            "--modules_exclude",
+            "store.mv",
+        ],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("Warning: Excluded modules are required by the bundle"));
+    assert!(output.contains("0x2::Store required by 0x2::StoreUser"));
+
+    dove(&["deploy", "--modules_exclude", "demo*"], &project_path).unwrap();
+    let modules = bundle_modules(&bundle_path);
+    assert!(modules.contains(&"0x2::Store".to_string()));
+    assert!(modules.contains(&"0x2::StoreUser".to_string()));
+    assert!(!modules.iter().any(|id| id.starts_with("0x2::Demo")));
+
+    assert!(dove(&["deploy", "--modules", "Unknown*"], &project_path).is_err());
+
+    delete_project(&project_path).unwrap();
+}
+
+/// Modules of the dependency packages
+/// $ dove deploy --include_deps DemoLib MoveStdlib
+/// $ dove deploy --include_deps Stale
+#[test]
+fn test_cmd_dove_deploy_include_deps() {
+    let project_name = "project_deploy_include_deps";
+    let project_path = new_demo_project(project_name).unwrap();
+    let lib_path = project_path.join("lib");
+    fs::create_dir_all(lib_path.join("sources")).unwrap();
+    fs::write(
+        lib_path.join("Move.toml"),
+        "[package]\nname = \"DemoLib\"\nversion = \"0.1.0\"\n\n[addresses]\nDemo = \"0x2\"\n",
+    )
+    .unwrap();
+    fs::write(
+        lib_path.join("sources").join("lib.move"),
+        "module Demo::Lib {
+            public fun value(): u64 { 1 }
+        }",
+    )
+    .unwrap();
+    let mut move_toml = fs::read_to_string(project_path.join("Move.toml")).unwrap();
+    move_toml.push_str("\nDemoLib = { local = \"./lib\" }\n");
+    fs::write(project_path.join("Move.toml"), move_toml).unwrap();
+    fs::write(
+        project_path.join("sources").join("store.move"),
+        "module Demo::StoreUser {
+            use Demo::Lib;
+            public fun run(): u64 { Lib::value() }
+        }",
+    )
+    .unwrap();
+    let bundle_path = project_path
+        .join("build")
+        .join("for_tests")
+        .join("bundles")
+        .join("for_tests.pac");
+
+    dove(&["deploy"], &project_path).unwrap();
+    let content = fs::read(&bundle_path).unwrap();
+    assert!(!find_u8(&content, b"0x2::Lib"));
+
+    let output = dove(
+        &["deploy", "--include_deps", "DemoLib", "MoveStdlib"],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("Including package 'DemoLib': 1 modules"));
+    let output = dove(&["package", "inspect"], &project_path).unwrap();
+    let lib = output.find("0x2::Lib\n").unwrap();
+    let user = output.find("0x2::StoreUser\n").unwrap();
+    assert!(lib < user);
+    assert!(!output.contains("0x1::"));
+
+    assert!(dove(&["deploy", "--include_deps", "Unknown"], &project_path).is_err());
+
+    // Build directories of other packages are not the project dependencies.
+    let stale_path = project_path
+        .join("build")
+        .join("Stale")
+        .join("bytecode_modules");
+    fs::create_dir_all(&stale_path).unwrap();
+    fs::copy(
+        project_path
+            .join("build")
+            .join("for_tests")
+            .join("bytecode_modules")
+            .join("StoreUser.mv"),
+        stale_path.join("StoreUser.mv"),
+    )
+    .unwrap();
+    let err = dove(&["deploy", "--include_deps", "Stale"], &project_path)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Dependency package 'Stale' not found"));
+
+    delete_project(&project_path).unwrap();
+}
+
+/// Init calls are checked before the publication
+/// $ dove deploy --account alice --gas 100 --init
+/// $ dove deploy --account alice --gas 100 --init 'Unknown::init()'
+#[test]
+fn test_cmd_dove_deploy_init_errors() {
+    let project_name = "project_deploy_init";
+    let project_path = new_demo_project(project_name).unwrap();
+    let args = ["deploy", "--account", "alice", "--gas", "100", "--init"];
+
+    let err = dove(&args, &project_path).unwrap_err();
+    assert!(err.to_string().contains("No init calls"));
+
+    let mut move_toml = fs::read_to_string(project_path.join("Move.toml")).unwrap();
+    move_toml.push_str("\n[dove.deploy]\ninit = [\"Unknown::init()\"]\n");
+    fs::write(project_path.join("Move.toml"), move_toml).unwrap();
+    let err = dove(&args, &project_path).unwrap_err();
+    assert!(err.to_string().contains("Invalid init call"));
+
+    let mut args = args.to_vec();
+    args.push("Unknown::init_2()");
+    let err = dove(&args, &project_path).unwrap_err();
+    assert!(err.to_string().contains("Invalid init call"));
+
+    delete_project(&project_path).unwrap();
+}
+
+/// Module ids from the bundle metadata in the dependency order.
+fn bundle_modules(bundle_path: &Path) -> Vec<String> {
+    Bundle::read(bundle_path)
+        .unwrap()
+        .metadata
+        .unwrap()
+        .modules
+        .into_iter()
+        .map(|module| module.id)
+        .collect()
+}
+
 fn find_u8(source: &[u8], need: &[u8]) -> bool {
     source.iter().enumerate().any(|(pos, _)| {
         need.iter()
@@ -44,3 +251,258 @@ fn find_u8(source: &[u8], need: &[u8]) -> bool {
             .all(|(index, byte)| Some(byte) == source.get(index + pos))
     })
 }
+
+/// The deploy plan queries the HTTP RPC of --url: ws://localhost:9944 => http://localhost:9933
+/// Without a node the error asks for the node api
+/// $ dove deploy --plan
+#[test]
+fn test_cmd_dove_deploy_plan_without_api() {
+    let project_name = "project_deploy_plan";
+    let project_path = new_demo_project(project_name).unwrap();
+
+    let err = dove(&["deploy", "--plan"], &project_path).unwrap_err();
+    assert!(err.to_string().contains("Specify --api"));
+
+    delete_project(&project_path).unwrap();
+}
+
+/// Modules compared with the on-chain code
+/// $ dove deploy --plan --api http://127.0.0.1:9933
+/// $ dove deploy for_tests.pac --account //Alice --url http://127.0.0.1:9933
+#[test]
+fn test_cmd_dove_deploy_plan() {
+    let project_name = "project_deploy_plan_api";
+    let project_path = new_demo_project(project_name).unwrap();
+    let modules_path = project_path
+        .join("build")
+        .join("for_tests")
+        .join("bytecode_modules");
+    let demo3v_path = project_path.join("sources").join("demo3v.move");
+    let demo3v = fs::read_to_string(&demo3v_path).unwrap();
+
+    // The deployed Demo3v has a public function that the local version lacks.
+    fs::write(
+        &demo3v_path,
+        "module Demo::Demo3v {
+            fun run() {}
+            public fun value(): u64 { 1 }
+        }",
+    )
+    .unwrap();
+    dove(&["build"], &project_path).unwrap();
+    let deployed_demo3v = fs::read(modules_path.join("Demo3v.mv")).unwrap();
+    fs::write(&demo3v_path, demo3v).unwrap();
+    dove(&["build"], &project_path).unwrap();
+    let demo1v = fs::read(modules_path.join("Demo1v.mv")).unwrap();
+
+    let api = start_node(move |method, params| {
+        let module_id = params[0].as_str().unwrap_or_default();
+        let module = if module_id.ends_with(&hex::encode("Demo1v")) {
+            &demo1v
+        } else if module_id.ends_with(&hex::encode("Demo3v")) {
+            &deployed_demo3v
+        } else {
+            return serde_json::Value::Null;
+        };
+        match method {
+            "mvm_getModule" => format!("0x{}", hex::encode(module)).into(),
+            _ => serde_json::Value::Null,
+        }
+    });
+
+    let output = dove(&["deploy", "--plan", "--api", &api], &project_path).unwrap();
+    assert!(output.contains("Deploy plan:"));
+    assert!(output.contains("    unchanged    0x2::Demo1v\n"));
+    assert!(output.contains("    new          0x2::Demo2v\n"));
+    assert!(output.contains("    incompatible 0x2::Demo3v\n"));
+    assert!(output.contains("        linking: public function value was removed\n"));
+
+    let err = dove(
+        &[
+            "deploy",
+            "for_tests.pac",
+            "--account",
+            "//Alice",
+            "--url",
+            &api,
+        ],
+        &project_path,
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Modules are incompatible with the on-chain code: 0x2::Demo3v"));
+
+    delete_project(&project_path).unwrap();
+}
+
+/// Modules already on chain are not published again
+/// $ dove deploy for_tests.pac --account //Alice --url http://127.0.0.1:9933
+#[test]
+fn test_cmd_dove_deploy_skip_unchanged() {
+    let project_name = "project_deploy_skip_unchanged";
+    let project_path = new_demo_project(project_name).unwrap();
+    dove(&["build"], &project_path).unwrap();
+    let modules_path = project_path
+        .join("build")
+        .join("for_tests")
+        .join("bytecode_modules");
+    let modules = ["Demo1v", "Demo2v", "Demo3v"]
+        .iter()
+        .map(|name| {
+            let bytecode = fs::read(modules_path.join(name).with_extension("mv")).unwrap();
+            (hex::encode(name), bytecode)
+        })
+        .collect::<Vec<_>>();
+
+    let api = start_node(move |method, params| {
+        let module_id = params[0].as_str().unwrap_or_default();
+        match (
+            method,
+            modules.iter().find(|(name, _)| module_id.ends_with(name)),
+        ) {
+            ("mvm_getModule", Some((_, bytecode))) => {
+                format!("0x{}", hex::encode(bytecode)).into()
+            }
+            _ => serde_json::Value::Null,
+        }
+    });
+
+    let output = dove(
+        &[
+            "deploy",
+            "for_tests.pac",
+            "--account",
+            "//Alice",
+            "--url",
+            &api,
+        ],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("    unchanged    0x2::Demo1v\n"));
+    assert!(output.contains("    unchanged    0x2::Demo2v\n"));
+    assert!(output.contains("    unchanged    0x2::Demo3v\n"));
+    assert!(output.contains("Nothing to publish: all modules are already on chain"));
+    assert!(!project_path
+        .join("build")
+        .join("for_tests")
+        .join("bundles")
+        .join("for_tests.changed.pac")
+        .exists());
+
+    delete_project(&project_path).unwrap();
+}
+
+/// Init calls are executed after the publication in the given order
+/// $ dove deploy for_tests.pac --account //Alice --gas 100 --url http://127.0.0.1:9933 --init 'Store::init(1)' 'Store::init(2)'
+#[test]
+fn test_cmd_dove_deploy_init() {
+    let project_name = "project_deploy_init_calls";
+    let project_path = new_demo_project(project_name).unwrap();
+    fs::write(
+        project_path.join("sources").join("store.move"),
+        "module Demo::Store {
+            public(script) fun init(_account: signer, _value: u64) {}
+        }",
+    )
+    .unwrap();
+
+    let api = substrate_node(usize::MAX);
+    let output = dove(
+        &[
+            "deploy",
+            "for_tests.pac",
+            "--account",
+            "//Alice",
+            "--gas",
+            "100",
+            "--url",
+            &api,
+            "--init",
+            "Store::init(1)",
+            "Store::init(2)",
+        ],
+        &project_path,
+    )
+    .unwrap();
+    let position = |line: &str| output.find(line).unwrap();
+    assert!(position(&format!("Address: {}", tx_hash(1))) < position("Init 1: Store::init(1)"));
+    assert!(position("Init 1: Store::init(1)") < position(&format!("Address: {}", tx_hash(2))));
+    assert!(position(&format!("Address: {}", tx_hash(2))) < position("Init 2: Store::init(2)"));
+    assert!(position("Init 2: Store::init(2)") < position(&format!("Address: {}", tx_hash(3))));
+
+    let registry = fs::read_to_string(project_path.join("deployments.toml")).unwrap();
+    let position = |line: &str| registry.find(line).unwrap();
+    assert!(position("name = \"for_tests\"") < position("name = \"Store::init(1)\""));
+    assert!(position("name = \"Store::init(1)\"") < position("name = \"Store::init(2)\""));
+
+    // The node rejects the first init call.
+    let api = substrate_node(2);
+    let err = dove(
+        &[
+            "deploy",
+            "for_tests.pac",
+            "--account",
+            "//Alice",
+            "--gas",
+            "100",
+            "--url",
+            &api,
+            "--init",
+            "Store::init(1)",
+            "Store::init(2)",
+        ],
+        &project_path,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("The modules were published, but the init call 'Store::init(1)' failed"));
+    assert!(err.contains("Not executed: Store::init(2)"));
+
+    delete_project(&project_path).unwrap();
+}
+
+/// Hash of the n-th submitted transaction.
+fn tx_hash(number: usize) -> String {
+    format!("0x{:064x}", number)
+}
+
+/// Node stand-in that accepts the transactions over HTTP.
+/// Modules are not on chain. The transactions starting from `fail_from` are rejected.
+fn substrate_node(fail_from: usize) -> String {
+    let metadata = fs::read(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("pontem")
+            .join("pontemapi")
+            .join("metadata")
+            .join("pontem.scale"),
+    )
+    .unwrap();
+    let submitted = Arc::new(AtomicUsize::new(0));
+    start_node(move |method, _| match method {
+        "state_getMetadata" => format!("0x{}", hex::encode(&metadata)).into(),
+        "chain_getBlockHash" => tx_hash(0).into(),
+        "state_getRuntimeVersion" | "chain_getRuntimeVersion" => serde_json::json!({
+            "specName": "pontem",
+            "implName": "pontem",
+            "authoringVersion": 1,
+            "specVersion": 1,
+            "implVersion": 1,
+            "apis": [],
+            "transactionVersion": 1,
+        }),
+        "system_properties" => serde_json::json!({}),
+        "system_accountNextIndex" => submitted.load(Ordering::SeqCst).into(),
+        "author_submitExtrinsic" => {
+            let number = submitted.fetch_add(1, Ordering::SeqCst) + 1;
+            if number < fail_from {
+                tx_hash(number).into()
+            } else {
+                serde_json::Value::Null
+            }
+        }
+        _ => serde_json::Value::Null,
+    })
+}
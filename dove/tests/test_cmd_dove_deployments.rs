@@ -0,0 +1,68 @@
+mod helpers;
+
+use std::fs;
+use helpers::{new_demo_project, dove, delete_project};
+
+/// $ dove deployments list
+/// $ dove deployments show 2
+#[test]
+fn test_cmd_dove_deployments() {
+    let project_name = "project_deployments";
+    let project_path = new_demo_project(project_name).unwrap();
+
+    let output = dove(&["deployments", "list"], &project_path).unwrap();
+    assert!(output.contains("No deployments"));
+
+    fs::write(
+        project_path.join("deployments.toml"),
+        r#"
+[[deployment]]
+kind = "bundle"
+name = "for_tests"
+network = "ws://127.0.0.1:9944/"
+signer = "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+hash = "a5b8a7e3c7a1f1bd8d0e64f3b1d6a8c2e0b8d2a5c5f7e9a1b3c5d7e9f1a3b5c7"
+tx_hash = "0x01"
+timestamp = "2021-12-01T10:00:00Z"
+dove_version = "1.5.0-abc"
+
+[[deployment.modules]]
+id = "0x2::Demo1v"
+hash = "b1"
+
+[[deployment]]
+kind = "call"
+name = "transfer"
+network = "ws://10.0.0.1:9944/"
+signer = "unknown"
+hash = "c1"
+tx_hash = "0x02"
+timestamp = "2021-12-02T10:00:00Z"
+dove_version = "1.5.0-abc"
+"#,
+    )
+    .unwrap();
+
+    let output = dove(&["deployments", "list"], &project_path).unwrap();
+    assert!(output.contains("for_tests"));
+    assert!(output.contains("transfer"));
+
+    let output = dove(
+        &["deployments", "list", "--network", "ws://127.0.0.1:9944"],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("for_tests"));
+    assert!(!output.contains("transfer"));
+
+    let output = dove(&["deployments", "show", "1"], &project_path).unwrap();
+    assert!(output.contains("Transaction: 0x01"));
+    assert!(output.contains("0x2::Demo1v b1"));
+
+    let output = dove(&["deployments", "show", "0x02"], &project_path).unwrap();
+    assert!(output.contains("Name: transfer"));
+
+    assert!(dove(&["deployments", "show", "3"], &project_path).is_err());
+
+    delete_project(&project_path).unwrap();
+}
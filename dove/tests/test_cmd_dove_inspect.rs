@@ -0,0 +1,52 @@
+mod helpers;
+
+use std::fs;
+use helpers::{new_demo_project, dove, delete_project};
+
+/// $ dove package inspect
+/// $ dove package inspect PATH/TO/MODULE.mv
+/// $ dove package inspect --dot
+#[test]
+fn test_cmd_dove_package_inspect() {
+    let project_name = "project_package_inspect";
+    let project_path = new_demo_project(project_name).unwrap();
+    fs::write(
+        project_path.join("sources").join("store.move"),
+        "module Demo::Store {
+            struct Counter has key, store { value: u64 }
+            public fun value(): u64 { 1 }
+        }
+        module Demo::StoreUser {
+            use Demo::Store;
+            public(script) fun run(_account: signer, v: u64) { let _ = Store::value() + v; }
+        }",
+    )
+    .unwrap();
+    dove(&["deploy"], &project_path).unwrap();
+
+    let output = dove(&["package", "inspect"], &project_path).unwrap();
+    assert!(output.contains("Package: for_tests 0.1.0"));
+    assert!(output.contains("0x2::Demo3v"));
+    assert!(output.contains("Counter has store + key"));
+    assert!(output.contains("run(signer, u64)"));
+    assert!(output.contains("Hash: "));
+
+    let output = dove(&["package", "inspect", "--dot"], &project_path).unwrap();
+    assert!(output.starts_with("digraph \"for_tests\" {"));
+    assert!(output.contains("\"0x2::StoreUser\" -> \"0x2::Store\";"));
+
+    let module_path = project_path
+        .join("build")
+        .join("for_tests")
+        .join("bytecode_modules")
+        .join("Store.mv");
+    let output = dove(
+        &["package", "inspect", &module_path.to_string_lossy()],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("Modules: 1"));
+    assert!(!output.contains("Package:"));
+
+    delete_project(&project_path).unwrap();
+}
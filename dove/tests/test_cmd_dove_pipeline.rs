@@ -0,0 +1,95 @@
+mod helpers;
+
+use std::fs;
+use helpers::{new_demo_project, dove, delete_project, start_node};
+
+/// $ dove pipeline run pipeline.toml --network local
+#[test]
+fn test_cmd_dove_pipeline_run() {
+    let project_name = "project_pipeline_run";
+    let project_path = new_demo_project(project_name).unwrap();
+    fs::write(
+        project_path.join("sources").join("Store.move"),
+        "module Demo::Store { struct U64 has key { value: u64 } }",
+    )
+    .unwrap();
+    dove(&["build"], &project_path).unwrap();
+    let module = fs::read(
+        project_path
+            .join("build")
+            .join("for_tests")
+            .join("bytecode_modules")
+            .join("Store.mv"),
+    )
+    .unwrap();
+    let api = start_node(move |method, _| match method {
+        "mvm_getModule" => format!("0x{}", hex::encode(&module)).into(),
+        "mvm_getResource" => format!("0x{}", hex::encode(100u64.to_le_bytes())).into(),
+        _ => serde_json::Value::Null,
+    });
+
+    let pipeline = format!(
+        r#"
+[network.local]
+api = "{api}"
+account = "0x2"
+vars = {{ expected = 100 }}
+
+[[step]]
+name = "build"
+build = true
+
+[[step]]
+name = "check"
+view = "${{account}}::Store::U64"
+expect = {{ value = "${{expected}}" }}
+save = {{ stored = "value" }}
+
+[[step]]
+view = "0x2::Store::U64"
+expect = {{ value = "${{stored}}" }}
+"#,
+        api = api
+    );
+    fs::write(project_path.join("pipeline.toml"), pipeline).unwrap();
+    let output = dove(
+        &["pipeline", "run", "pipeline.toml", "--network", "local"],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("Pipeline check:"));
+    assert!(output.contains("Pipeline completed"));
+
+    fs::write(
+        project_path.join("failed.toml"),
+        format!(
+            r#"
+[network.local]
+api = "{api}"
+
+[[step]]
+view = "0x2::Store::U64"
+expect = {{ value = 200 }}
+"#,
+            api = api
+        ),
+    )
+    .unwrap();
+    let err = dove(
+        &["pipeline", "run", "failed.toml", "--network", "local"],
+        &project_path,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("expected 200, got 100"));
+
+    let err = dove(
+        &["pipeline", "run", "failed.toml", "--network", "testnet"],
+        &project_path,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("Network 'testnet' not found"));
+
+    delete_project(&project_path).unwrap();
+}
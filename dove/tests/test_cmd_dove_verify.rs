@@ -0,0 +1,63 @@
+mod helpers;
+
+use std::fs;
+use helpers::{new_demo_project, dove, delete_project, start_node};
+
+/// $ dove verify --address 0x2 --api http://127.0.0.1:9933
+/// $ dove verify --address 0x2 --module Demo1v --api http://127.0.0.1:9933
+#[test]
+fn test_cmd_dove_verify() {
+    let project_name = "project_verify";
+    let project_path = new_demo_project(project_name).unwrap();
+    dove(&["build"], &project_path).unwrap();
+    let modules_path = project_path
+        .join("build")
+        .join("for_tests")
+        .join("bytecode_modules");
+    let demo1v = fs::read(modules_path.join("Demo1v.mv")).unwrap();
+    let demo3v = fs::read(modules_path.join("Demo3v.mv")).unwrap();
+
+    // Demo1v is deployed from the same sources, Demo2v differs, Demo3v is not deployed.
+    let api = start_node(move |method, params| {
+        let module_id = params[0].as_str().unwrap_or_default();
+        let module = if method != "mvm_getModule" {
+            return serde_json::Value::Null;
+        } else if module_id.ends_with(&hex::encode("Demo1v")) {
+            &demo1v
+        } else if module_id.ends_with(&hex::encode("Demo2v")) {
+            &demo3v
+        } else {
+            return serde_json::Value::Null;
+        };
+        format!("0x{}", hex::encode(module)).into()
+    });
+
+    let err = dove(
+        &["verify", "--address", "0x2", "--api", &api],
+        &project_path,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("    0x2::Demo1v: match\n"));
+    assert!(err.contains("    0x2::Demo2v: mismatch\n"));
+    assert!(err.contains("    0x2::Demo3v: not found on chain\n"));
+    assert!(err.contains("The deployed modules do not match the sources"));
+
+    let output = dove(
+        &[
+            "verify",
+            "--address",
+            "0x2",
+            "--module",
+            "Demo1v",
+            "--api",
+            &api,
+        ],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("    0x2::Demo1v: match\n"));
+    assert!(output.contains("All modules match the sources"));
+
+    delete_project(&project_path).unwrap();
+}
@@ -0,0 +1,481 @@
+mod helpers;
+
+use std::fs;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use helpers::{new_demo_project, dove, delete_project, move_home, start_node};
+
+/// $ dove view 0x2::Store::U64 --local
+/// $ dove view --account 0x2 --all --local
+#[test]
+fn test_cmd_dove_view_local() {
+    let project_name = "project_view_local";
+    let project_path = new_demo_project(project_name).unwrap();
+    fs::write(
+        project_path.join("sources").join("Store.move"),
+        "module Demo::Store {
+            struct U64 has key { value: u64 }
+
+            public fun store_u64(account: &signer, value: u64) {
+                move_to(account, U64 { value });
+            }
+        }",
+    )
+    .unwrap();
+    fs::write(
+        project_path.join("scripts").join("store_u64.move"),
+        "script {
+            use Demo::Store;
+
+            fun store_u64(account: signer, value: u64) {
+                Store::store_u64(&account, value);
+            }
+        }",
+    )
+    .unwrap();
+
+    assert!(dove(&["view", "0x2::Store::U64", "--local"], &project_path).is_err());
+
+    dove(&["run", "store_u64(0x2, 100)"], &project_path).unwrap();
+    let output = dove(&["view", "0x2::Store::U64", "--local"], &project_path).unwrap();
+    assert!(output.contains("100"));
+
+    let output = dove(
+        &["view", "0x2::Store::U64", "--local", "--json"],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("\"value\""));
+
+    let output = dove(
+        &["view", "--account", "0x2", "--all", "--local"],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("Resources of 0x2: 1"));
+    assert!(output.contains("value: 100"));
+
+    // The node can't list the resources of an account.
+    assert!(dove(&["view", "--account", "0x2", "--all"], &project_path).is_err());
+
+    delete_project(&project_path).unwrap();
+}
+
+/// $ dove view 0x2::Store::U64 --account 0x3 --local
+/// $ dove view 0x3@0x2::Store::U64 --local
+#[test]
+fn test_cmd_dove_view_account() {
+    let project_name = "project_view_account";
+    let project_path = new_demo_project(project_name).unwrap();
+    fs::write(
+        project_path.join("sources").join("Store.move"),
+        "module Demo::Store {
+            struct U64 has key { value: u64 }
+
+            public fun store_u64(account: &signer, value: u64) {
+                move_to(account, U64 { value });
+            }
+        }",
+    )
+    .unwrap();
+    fs::write(
+        project_path.join("scripts").join("store_u64.move"),
+        "script {
+            use Demo::Store;
+
+            fun store_u64(account: signer, value: u64) {
+                Store::store_u64(&account, value);
+            }
+        }",
+    )
+    .unwrap();
+    dove(&["run", "store_u64(0x3, 42)"], &project_path).unwrap();
+
+    assert!(dove(&["view", "0x2::Store::U64", "--local"], &project_path).is_err());
+
+    let output = dove(
+        &["view", "0x2::Store::U64", "--account", "0x3", "--local"],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("42"));
+
+    let output = dove(&["view", "0x3@0x2::Store::U64", "--local"], &project_path).unwrap();
+    assert!(output.contains("42"));
+
+    assert!(dove(
+        &["view", "0x3@0x2::Store::U64", "--account", "0x3", "--local"],
+        &project_path
+    )
+    .is_err());
+
+    delete_project(&project_path).unwrap();
+}
+
+/// $ dove view 0x2::Store::U64 --from 100 --to 104 --api http://127.0.0.1:9933
+/// $ dove view 0x2::Store::U64 --diff 102 104 --api http://127.0.0.1:9933
+#[test]
+fn test_cmd_dove_view_history() {
+    let project_name = "project_view_history";
+    let project_path = new_demo_project(project_name).unwrap();
+    fs::write(
+        project_path.join("sources").join("Store.move"),
+        "module Demo::Store { struct U64 has key { value: u64 } }",
+    )
+    .unwrap();
+    dove(&["build"], &project_path).unwrap();
+    let module = fs::read(
+        project_path
+            .join("build")
+            .join("for_tests")
+            .join("bytecode_modules")
+            .join("Store.mv"),
+    )
+    .unwrap();
+
+    // The block hash is the block number: 0x64 is the block 100.
+    let hash_requests = Arc::new(AtomicUsize::new(0));
+    let counter = hash_requests.clone();
+    let api = start_node(move |method, params| match method {
+        "chain_getBlockHash" => {
+            if params[0] != "0x0" {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+            params[0].clone()
+        }
+        "mvm_getModule" => format!("0x{}", hex::encode(&module)).into(),
+        "mvm_getResource" => {
+            let block = params[2]
+                .as_str()
+                .and_then(|hash| u64::from_str_radix(hash.trim_start_matches("0x"), 16).ok())
+                .unwrap_or_default();
+            match block {
+                0..=101 => serde_json::Value::Null,
+                102..=103 => format!("0x{}", hex::encode(100u64.to_le_bytes())).into(),
+                _ => format!("0x{}", hex::encode(110u64.to_le_bytes())).into(),
+            }
+        }
+        _ => serde_json::Value::Null,
+    });
+
+    let output = dove(
+        &[
+            "view",
+            "0x2::Store::U64",
+            "--from",
+            "100",
+            "--to",
+            "104",
+            "--api",
+            &api,
+        ],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("Height 100: not found"));
+    assert!(output.contains("Height 102: created\n    + value: 100"));
+    assert!(output.contains("Height 103: no changes"));
+    assert!(output.contains("Height 104:\n    value: 100 -> 110"));
+    // Each block is resolved once for the resource and the modules.
+    assert_eq!(hash_requests.load(Ordering::SeqCst), 5);
+
+    let output = dove(
+        &[
+            "view",
+            "0x2::Store::U64",
+            "--diff",
+            "102",
+            "104",
+            "--api",
+            &api,
+            "--json",
+        ],
+        &project_path,
+    )
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json[1]["changes"][0]["change"], "changed");
+    assert_eq!(json[1]["changes"][0]["new"], "110");
+
+    delete_project(&project_path).unwrap();
+}
+
+/// $ dove view 0x2::Info::Info --local --format yaml
+/// $ dove view 0x2::Info::Info --local --format table
+/// $ dove view 0x2::Info::Info --local --format csv
+#[test]
+fn test_cmd_dove_view_formats() {
+    let project_name = "project_view_formats";
+    let project_path = new_demo_project(project_name).unwrap();
+    fs::write(
+        project_path.join("sources").join("Info.move"),
+        "module Demo::Info {
+            use Std::ASCII;
+            use Std::Option::{Self, Option};
+            use Std::Signer;
+
+            struct Info has key {
+                name: vector<u8>,
+                symbol: ASCII::String,
+                cap: Option<u64>,
+                supply: Option<u64>,
+                owner: address,
+            }
+
+            public fun store(account: &signer) {
+                move_to(account, Info {
+                    name: b\"Pontem\",
+                    symbol: ASCII::string(b\"PONT\"),
+                    cap: Option::none(),
+                    supply: Option::some(100),
+                    owner: Signer::address_of(account),
+                });
+            }
+        }",
+    )
+    .unwrap();
+    fs::write(
+        project_path.join("scripts").join("store_info.move"),
+        "script {
+            use Demo::Info;
+
+            fun store_info(account: signer) {
+                Info::store(&account);
+            }
+        }",
+    )
+    .unwrap();
+    dove(&["run", "store_info(0x2)"], &project_path).unwrap();
+
+    let output = dove(
+        &["view", "0x2::Info::Info", "--local", "--format", "yaml"],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("type: \"0x2::Info::Info\""));
+    assert!(output.contains("  name: \"Pontem\""));
+    assert!(output.contains("  symbol: \"PONT\""));
+    assert!(output.contains("  cap: null"));
+    assert!(output.contains("  supply: 100"));
+    assert!(output.contains("  owner: \"0x2\""));
+
+    let output = dove(
+        &["view", "0x2::Info::Info", "--local", "--format", "table"],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("symbol"));
+    assert!(output.contains("PONT"));
+
+    let output = dove(
+        &[
+            "view",
+            "0x2::Info::Info",
+            "--local",
+            "--format",
+            "csv",
+            "--address-format",
+            "ss58",
+        ],
+        &project_path,
+    )
+    .unwrap();
+    assert!(output.contains("field,value"));
+    assert!(output.contains("cap,none"));
+    assert!(output.contains("supply,100"));
+    assert!(!output.contains("owner,0x2"));
+
+    assert!(dove(
+        &[
+            "view",
+            "--account",
+            "0x2",
+            "--all",
+            "--local",
+            "--format",
+            "csv"
+        ],
+        &project_path
+    )
+    .is_err());
+
+    delete_project(&project_path).unwrap();
+}
+
+/// $ dove view 0x2::Store::Big --local --json --js-safe --json-schema schema.json
+#[test]
+fn test_cmd_dove_view_js_safe() {
+    let project_name = "project_view_js_safe";
+    let project_path = new_demo_project(project_name).unwrap();
+    fs::write(
+        project_path.join("sources").join("Store.move"),
+        "module Demo::Store {
+            use Std::Signer;
+            use Std::Vector;
+
+            struct Big has key { value: u128, items: vector<u64>, owner: address }
+
+            public fun store(account: &signer) {
+                move_to(account, Big {
+                    value: 340282366920938463463374607431768211455,
+                    items: Vector::singleton(18446744073709551615),
+                    owner: Signer::address_of(account),
+                });
+            }
+        }",
+    )
+    .unwrap();
+    fs::write(
+        project_path.join("scripts").join("store_big.move"),
+        "script {
+            use Demo::Store;
+
+            fun store_big(account: signer) {
+                Store::store(&account);
+            }
+        }",
+    )
+    .unwrap();
+    dove(&["run", "store_big(0x2)"], &project_path).unwrap();
+
+    let schema_path = project_path.join("schema.json");
+    let output = dove(
+        &[
+            "view",
+            "0x2::Store::Big",
+            "--local",
+            "--json",
+            "--js-safe",
+            "--json-schema",
+            schema_path.to_str().unwrap(),
+        ],
+        &project_path,
+    )
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let result = &json["result"];
+    assert_eq!(result["type"], "0x2::Store::Big");
+    assert_eq!(result["value"][0]["id"], "value");
+    assert_eq!(
+        result["value"][0]["value"]["U128"],
+        "340282366920938463463374607431768211455"
+    );
+    assert_eq!(result["value"][1]["value"]["Vector"][0], "u64");
+    assert_eq!(
+        result["value"][1]["value"]["Vector"][1][0]["U64"],
+        "18446744073709551615"
+    );
+    assert_eq!(result["value"][2]["value"]["Address"], "0x2");
+
+    let schema = fs::read_to_string(&schema_path).unwrap();
+    assert!(schema.contains("JsMoveValue"));
+
+    let output = dove(
+        &[
+            "view",
+            "--account",
+            "0x2",
+            "--all",
+            "--local",
+            "--json",
+            "--js-safe",
+        ],
+        &project_path,
+    )
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json[0]["result"]["type"], "0x2::Store::Big");
+
+    // Not JSON.
+    let err = dove(
+        &["view", "0x2::Store::Big", "--local", "--js-safe"],
+        &project_path,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("--js-safe requires --json"));
+    let err = dove(
+        &[
+            "view",
+            "0x2::Store::Big",
+            "--diff",
+            "1",
+            "2",
+            "--json",
+            "--js-safe",
+        ],
+        &project_path,
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("--js-safe is not supported by --from and --diff"));
+
+    delete_project(&project_path).unwrap();
+}
+
+/// $ dove view 0x2::Store::U64 --api http://127.0.0.1:9933
+/// $ dove view 0x2::Store::U64 --api http://127.0.0.1:9933 --no-cache
+#[test]
+fn test_cmd_dove_view_module_cache() {
+    let project_name = "project_view_module_cache";
+    let project_path = new_demo_project(project_name).unwrap();
+    fs::write(
+        project_path.join("sources").join("Store.move"),
+        "module Demo::Store { struct U64 has key { value: u64 } }",
+    )
+    .unwrap();
+    dove(&["build"], &project_path).unwrap();
+    let module = fs::read(
+        project_path
+            .join("build")
+            .join("for_tests")
+            .join("bytecode_modules")
+            .join("Store.mv"),
+    )
+    .unwrap();
+
+    // A unique genesis: the cache of the other runs is not used.
+    let genesis = format!(
+        "0x{:x}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    );
+    let module_requests = Arc::new(AtomicUsize::new(0));
+    let api = {
+        let genesis = genesis.clone();
+        let module_requests = module_requests.clone();
+        start_node(move |method, _| match method {
+            "chain_getBlockHash" => genesis.clone().into(),
+            "mvm_getModule" => {
+                module_requests.fetch_add(1, Ordering::SeqCst);
+                format!("0x{}", hex::encode(&module)).into()
+            }
+            "mvm_getResource" => format!("0x{}", hex::encode(100u64.to_le_bytes())).into(),
+            _ => serde_json::Value::Null,
+        })
+    };
+
+    for _ in 0..2 {
+        let output = dove(&["view", "0x2::Store::U64", "--api", &api], &project_path).unwrap();
+        assert!(output.contains("100"));
+    }
+    assert_eq!(module_requests.load(Ordering::SeqCst), 1);
+
+    dove(
+        &["view", "0x2::Store::U64", "--api", &api, "--no-cache"],
+        &project_path,
+    )
+    .unwrap();
+    assert_eq!(module_requests.load(Ordering::SeqCst), 2);
+
+    let cache_dir = move_home(&project_path)
+        .join("cache")
+        .join("modules")
+        .join(&genesis);
+    assert!(cache_dir.exists());
+
+    delete_project(&project_path).unwrap();
+}
@@ -0,0 +1,278 @@
+use std::fmt;
+use anyhow::Error;
+use move_core_types::account_address::AccountAddress;
+use move_package::source_package::parsed_manifest::{AddressDeclarations, NamedAddress};
+use crate::ss58::ss58_to_address;
+
+/// Resolves names unknown to the resolver itself, e.g. wallet key aliases.
+pub type AliasResolver = Box<dyn Fn(&str) -> Result<Option<AccountAddress>, Error>>;
+
+/// Development accounts of the substrate node.
+pub const DEV_ACCOUNTS: [(&str, &str); 6] = [
+    (
+        "alice",
+        "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+    ),
+    (
+        "bob",
+        "0x8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48",
+    ),
+    (
+        "charlie",
+        "0x90b5ab205c6974c9ea841be688864633dc9ca8a357843eeacf2314649965fe22",
+    ),
+    (
+        "dave",
+        "0x306721211d5404bd9da88e0204360a1a9ab8b87c66c1bc2fcdd37f3c2222cc20",
+    ),
+    (
+        "eve",
+        "0xe659a7a1628cdd93febc04a4e0646ea20e9f5f0ce097d9a05290d4a9e054df4e",
+    ),
+    (
+        "ferdie",
+        "0x1cbd2d43530a44705ad088af313e18f80b53ef16b36177cd4b77b846f2a5f07c",
+    ),
+];
+
+/// Returns the address of the development account: //Alice, alice, bob...
+pub fn dev_account(name: &str) -> Option<AccountAddress> {
+    let name = name.trim_start_matches("//").to_lowercase();
+    DEV_ACCOUNTS
+        .iter()
+        .find(|(dev_name, _)| *dev_name == name)
+        .and_then(|(_, address)| AccountAddress::from_hex_literal(address).ok())
+}
+
+/// Address resolver.
+/// Understands hex and SS58 addresses, named addresses from Move.toml,
+/// development accounts (//Alice, alice) and, for accounts only, aliases such as wallet keys.
+#[derive(Default)]
+pub struct AddressResolver {
+    named: AddressDeclarations,
+    aliases: Option<AliasResolver>,
+}
+
+impl AddressResolver {
+    /// Creates a resolver with the named addresses from Move.toml.
+    pub fn new(named: AddressDeclarations) -> AddressResolver {
+        AddressResolver {
+            named,
+            aliases: None,
+        }
+    }
+
+    /// Sets the resolver of aliases.
+    pub fn with_aliases(mut self, aliases: AliasResolver) -> AddressResolver {
+        self.aliases = Some(aliases);
+        self
+    }
+
+    /// Returns the named addresses from Move.toml.
+    pub fn named_addresses(&self) -> &AddressDeclarations {
+        &self.named
+    }
+
+    /// Resolves an address of a module or a type:
+    ///     0x1, 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY, Std, //Alice, alice
+    /// Aliases are not used: see [AddressResolver::resolve_account].
+    pub fn resolve(&self, address: &str) -> Result<AccountAddress, Error> {
+        self.resolve_address(address, false)
+    }
+
+    /// Resolves an account address: signers, senders, --account, address arguments.
+    /// In addition to [AddressResolver::resolve] understands the aliases such as wallet keys:
+    ///     0x1, 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY, Std, //Alice, alice, WALLET_KEY
+    pub fn resolve_account(&self, address: &str) -> Result<AccountAddress, Error> {
+        self.resolve_address(address, true)
+    }
+
+    fn resolve_address(
+        &self,
+        address: &str,
+        with_aliases: bool,
+    ) -> Result<AccountAddress, Error> {
+        let address = address.trim();
+        if address.starts_with("0x") {
+            return AccountAddress::from_hex_literal(address)
+                .map_err(|err| anyhow!("Invalid address {}: {}", address, err));
+        }
+
+        if let Some(resolved) = self.resolve_name(address)? {
+            return Ok(resolved);
+        }
+
+        if with_aliases {
+            if let Some(resolved) = self.resolve_alias(address)? {
+                return Ok(resolved);
+            }
+        }
+
+        ss58_to_address(address).map_err(|_| {
+            if with_aliases {
+                anyhow!(
+                    "Failed to resolve address '{}'. Expected a hex or SS58 address, \
+                    a named address from Move.toml, a wallet key or a dev account (//Alice).",
+                    address
+                )
+            } else {
+                anyhow!(
+                    "Failed to resolve address '{}'. Expected a hex or SS58 address, \
+                    a named address from Move.toml or a dev account (//Alice).",
+                    address
+                )
+            }
+        })
+    }
+
+    /// Resolves a named address or a dev account.
+    pub fn resolve_name(&self, name: &str) -> Result<Option<AccountAddress>, Error> {
+        if let Some(address) = self.named.get(&NamedAddress::from(name)) {
+            return address
+                .map(Some)
+                .ok_or_else(|| anyhow!("In Move.toml address not assigned to alias {}", name));
+        }
+
+        Ok(dev_account(name))
+    }
+
+    /// Resolves an alias, e.g. a wallet key.
+    fn resolve_alias(&self, name: &str) -> Result<Option<AccountAddress>, Error> {
+        match &self.aliases {
+            Some(aliases) => aliases(name),
+            None => Ok(None),
+        }
+    }
+
+    /// Replaces the leading address of the path with the hex address
+    /// if the address can't be parsed by the Move lexer (SS58 address, //Alice):
+    ///     5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY::Module::func => 0xd435...::Module::func
+    pub fn replace_leading_address(&self, path: &str) -> Result<String, Error> {
+        let path = path.trim();
+        if let Some(pos) = path.find("::") {
+            let prefix = &path[..pos];
+            let is_address = !prefix.is_empty()
+                && !prefix.starts_with("0x")
+                && !is_identifier(prefix)
+                && !prefix.contains(|ch: char| "<>(),: ".contains(ch));
+            if is_address {
+                let address = self.resolve(prefix)?;
+                return Ok(format!("{}{}", address.to_hex_literal(), &path[pos..]));
+            }
+        }
+        Ok(path.to_string())
+    }
+}
+
+impl fmt::Debug for AddressResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AddressResolver")
+            .field("named", &self.named)
+            .field("aliases", &self.aliases.is_some())
+            .finish()
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(ch) if ch.is_ascii_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::account_address::AccountAddress;
+    use move_package::source_package::parsed_manifest::{AddressDeclarations, NamedAddress};
+    use crate::address::{AddressResolver, dev_account};
+
+    fn alice() -> AccountAddress {
+        AccountAddress::from_hex_literal(
+            "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+        )
+        .unwrap()
+    }
+
+    fn one() -> AccountAddress {
+        AccountAddress::from_hex_literal("0x1").unwrap()
+    }
+
+    fn resolver() -> AddressResolver {
+        let mut named = AddressDeclarations::new();
+        named.insert(NamedAddress::from("Std"), Some(one()));
+        named.insert(NamedAddress::from("Unassigned"), None);
+        AddressResolver::new(named).with_aliases(Box::new(|name| {
+            Ok(if name == "my_key" {
+                Some(alice())
+            } else {
+                None
+            })
+        }))
+    }
+
+    #[test]
+    fn test_dev_account() {
+        assert_eq!(dev_account("//Alice"), Some(alice()));
+        assert_eq!(dev_account("alice"), Some(alice()));
+        assert_eq!(dev_account("//Zed"), None);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let resolver = resolver();
+        assert_eq!(resolver.resolve("0x1").unwrap(), one());
+        assert_eq!(resolver.resolve("Std").unwrap(), one());
+        assert_eq!(resolver.resolve("//Alice").unwrap(), alice());
+        assert!(resolver.resolve("my_key").is_err());
+        assert_eq!(resolver.resolve_account("my_key").unwrap(), alice());
+        assert_eq!(resolver.resolve_account("Std").unwrap(), one());
+        assert_eq!(
+            resolver
+                .resolve("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+                .unwrap(),
+            alice()
+        );
+        assert!(resolver.resolve("Unassigned").is_err());
+        assert!(resolver.resolve("Unknown").is_err());
+        assert!(resolver.resolve("0xZZ").is_err());
+    }
+
+    #[test]
+    fn test_resolve_name_without_aliases() {
+        let resolver = AddressResolver::default().with_aliases(Box::new(|name| {
+            panic!("Alias {} must not be resolved", name)
+        }));
+        assert_eq!(resolver.resolve_name("Unknown").unwrap(), None);
+        assert!(resolver.resolve("Unknown").is_err());
+        assert!(resolver.replace_leading_address("Unknown::M::T").is_ok());
+    }
+
+    #[test]
+    fn test_replace_leading_address() {
+        let resolver = resolver();
+        let alice = alice().to_hex_literal();
+        assert_eq!(
+            resolver
+                .replace_leading_address(
+                    "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY::M::f()"
+                )
+                .unwrap(),
+            format!("{}::M::f()", alice)
+        );
+        assert_eq!(
+            resolver.replace_leading_address("//Alice::M::T").unwrap(),
+            format!("{}::M::T", alice)
+        );
+        assert_eq!(
+            resolver.replace_leading_address("Std::M::T").unwrap(),
+            "Std::M::T"
+        );
+        assert_eq!(
+            resolver.replace_leading_address("0x1::M::T").unwrap(),
+            "0x1::M::T"
+        );
+        assert_eq!(
+            resolver.replace_leading_address("main(1, 2)").unwrap(),
+            "main(1, 2)"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use std::fmt;
 use anyhow::Error;
 use move_binary_format::access::{ModuleAccess, ScriptAccess};
 use move_binary_format::CompiledModule;
@@ -54,6 +55,40 @@ impl BytecodeInfo {
         }
     }
 
+    /// Returns the definition index of the function with the given name.
+    /// A script has a single function with index 0.
+    pub fn function_index(&self, need_name: &str) -> Option<u16> {
+        match &self.bytecode {
+            Bytecode::Script(name, _, _, _) => (name == need_name).then_some(0),
+            Bytecode::Module(module, _) => module
+                .function_defs()
+                .iter()
+                .position(|def| {
+                    let handle = module.function_handle_at(def.function);
+                    module.identifier_at(handle.name).as_str() == need_name
+                })
+                .map(|idx| idx as u16),
+        }
+    }
+
+    /// Returns all functions that can be called by a transaction.
+    pub fn script_functions(&self) -> Vec<Script> {
+        match &self.bytecode {
+            Bytecode::Script(name, _, _, _) => {
+                self.find_script_function(name).into_iter().collect()
+            }
+            Bytecode::Module(module, _) => module
+                .function_defs()
+                .iter()
+                .filter(|def| def.visibility == Visibility::Script)
+                .filter_map(|def| {
+                    let handle = module.function_handle_at(def.function);
+                    self.find_script_function(module.identifier_at(handle.name).as_str())
+                })
+                .collect(),
+        }
+    }
+
     pub fn find_script_function(&self, need_name: &str) -> Option<Script> {
         match &self.bytecode {
             Bytecode::Script(name, script, module, _) => {
@@ -145,6 +180,24 @@ pub enum Type {
     TypeParameter(u16),
 }
 
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Bool => write!(f, "bool"),
+            Type::U8 => write!(f, "u8"),
+            Type::U64 => write!(f, "u64"),
+            Type::U128 => write!(f, "u128"),
+            Type::Address => write!(f, "address"),
+            Type::Signer => write!(f, "signer"),
+            Type::Vector(tp) => write!(f, "vector<{}>", tp),
+            Type::Struct(def) => write!(f, "{}", def),
+            Type::Reference(tp) => write!(f, "&{}", tp),
+            Type::MutableReference(tp) => write!(f, "&mut {}", tp),
+            Type::TypeParameter(idx) => write!(f, "T{}", idx),
+        }
+    }
+}
+
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub struct StructDef {
     pub address: AccountAddress,
@@ -153,6 +206,27 @@ pub struct StructDef {
     pub type_parameters: Vec<Type>,
 }
 
+impl fmt::Display for StructDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::{}::{}",
+            self.address.to_hex_literal(),
+            self.module_name,
+            self.name
+        )?;
+        if !self.type_parameters.is_empty() {
+            let type_parameters = self
+                .type_parameters
+                .iter()
+                .map(|tp| tp.to_string())
+                .collect::<Vec<_>>();
+            write!(f, "<{}>", type_parameters.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
 fn make_type(tok: &SignatureToken, module: &CompiledModule) -> Type {
     match tok {
         SignatureToken::Bool => Type::Bool,
@@ -211,6 +285,17 @@ impl From<&AbilitySet> for TypeAbilities {
     }
 }
 
+impl fmt::Display for TypeAbilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let abilities = self
+            .abilities
+            .iter()
+            .map(|ability| ability.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", abilities.join(" + "))
+    }
+}
+
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub enum TypeAbility {
     Copy,
@@ -218,3 +303,14 @@ pub enum TypeAbility {
     Store,
     Key,
 }
+
+impl fmt::Display for TypeAbility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeAbility::Copy => write!(f, "copy"),
+            TypeAbility::Drop => write!(f, "drop"),
+            TypeAbility::Store => write!(f, "store"),
+            TypeAbility::Key => write!(f, "key"),
+        }
+    }
+}
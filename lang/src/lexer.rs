@@ -3,11 +3,11 @@ use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
 use move_compiler::parser::ast::{LeadingNameAccess_, NameAccessChain_, Type, Type_};
-use move_package::source_package::parsed_manifest::AddressDeclarations;
+use crate::address::AddressResolver;
 
-pub fn unwrap_spanned_ty(addr_map: &AddressDeclarations, ty: Type) -> Result<TypeTag, Error> {
+pub fn unwrap_spanned_ty(resolver: &AddressResolver, ty: Type) -> Result<TypeTag, Error> {
     fn unwrap_spanned_ty_(
-        addr_map: &AddressDeclarations,
+        resolver: &AddressResolver,
         ty: Type,
         this: Option<AccountAddress>,
     ) -> Result<TypeTag, Error> {
@@ -23,7 +23,7 @@ pub fn unwrap_spanned_ty(addr_map: &AddressDeclarations, ty: Type) -> Result<Typ
                         "address" => TypeTag::Address,
                         "signer" => TypeTag::Signer,
                         "Vec" if ty_params.len() == 1 => TypeTag::Vector(
-                            unwrap_spanned_ty_(addr_map, ty_params.pop().unwrap(), this)
+                            unwrap_spanned_ty_(resolver, ty_params.pop().unwrap(), this)
                                 .unwrap()
                                 .into(),
                         ),
@@ -39,8 +39,7 @@ pub fn unwrap_spanned_ty(addr_map: &AddressDeclarations, ty: Type) -> Result<Typ
                         let address = match addr.value {
                             LeadingNameAccess_::AnonymousAddress(addr) => AccountAddress::new(addr.into_bytes()),
                             LeadingNameAccess_::Name(name) => {
-                                addr_map.get(&name.value)
-                                    .and_then(|addr|addr.to_owned())
+                                resolver.resolve_name(name.value.as_str())?
                                     .ok_or_else(|| anyhow!("Could not parse input: unsupported named address. Name '{}'.", name))?
                             }
                         };
@@ -50,7 +49,7 @@ pub fn unwrap_spanned_ty(addr_map: &AddressDeclarations, ty: Type) -> Result<Typ
                             name: Identifier::new(name.value.as_str())?,
                             type_params: ty_params
                                 .into_iter()
-                                .map(|ty| unwrap_spanned_ty_(addr_map, ty, Some(address)))
+                                .map(|ty| unwrap_spanned_ty_(resolver, ty, Some(address)))
                                 .map(|res| match res {
                                     Ok(st) => st,
                                     Err(err) => panic!("{:?}", err),
@@ -72,7 +71,7 @@ pub fn unwrap_spanned_ty(addr_map: &AddressDeclarations, ty: Type) -> Result<Typ
                             name: Identifier::new(name.value.as_str())?,
                             type_params: ty_params
                                 .into_iter()
-                                .map(|ty| unwrap_spanned_ty_(addr_map, ty, Some(address)))
+                                .map(|ty| unwrap_spanned_ty_(resolver, ty, Some(address)))
                                 .map(|res| match res {
                                     Ok(st) => st,
                                     Err(err) => panic!("{:?}", err),
@@ -90,7 +89,7 @@ pub fn unwrap_spanned_ty(addr_map: &AddressDeclarations, ty: Type) -> Result<Typ
         Ok(st)
     }
 
-    unwrap_spanned_ty_(addr_map, ty, None)
+    unwrap_spanned_ty_(resolver, ty, None)
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate anyhow;
 
+pub mod address;
 pub mod bytecode;
 pub mod lexer;
 pub mod ss58;
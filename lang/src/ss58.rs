@@ -6,17 +6,108 @@ const SS58_PREFIX: &[u8] = b"SS58PRE";
 const PUB_KEY_LENGTH: usize = 32;
 const CHECK_SUM_LEN: usize = 2;
 
-/// Convert address to ss58
+/// Network prefix of the generic substrate address.
+pub const DEFAULT_SS58_PREFIX: u16 = 42;
+/// The largest network prefix that fits into two bytes.
+const MAX_SS58_PREFIX: u16 = 0b0011_1111_1111_1111;
+/// Prefixes reserved by the SS58 format.
+const RESERVED_SS58_PREFIXES: [u16; 2] = [46, 47];
+
+/// Well-known networks and their SS58 prefixes.
+pub const SS58_NETWORKS: [(&str, u16); 4] = [
+    ("polkadot", 0),
+    ("kusama", 2),
+    ("substrate", DEFAULT_SS58_PREFIX),
+    ("pontem", 105),
+];
+
+/// Checks that the network prefix can be used in an SS58 address.
+pub fn validate_prefix(prefix: u16) -> Result<()> {
+    ensure!(
+        prefix <= MAX_SS58_PREFIX,
+        "Invalid SS58 prefix {}. The prefix must be less than {}",
+        prefix,
+        MAX_SS58_PREFIX + 1
+    );
+    ensure!(
+        !RESERVED_SS58_PREFIXES.contains(&prefix),
+        "Invalid SS58 prefix {}. The prefix is reserved",
+        prefix
+    );
+    Ok(())
+}
+
+/// Returns the SS58 prefix of the network: pontem, polkadot, 105...
+pub fn network_prefix(network: &str) -> Result<u16> {
+    let network = network.trim();
+    let prefix = match network.parse::<u16>() {
+        Ok(prefix) => prefix,
+        Err(_) => SS58_NETWORKS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(network))
+            .map(|(_, prefix)| *prefix)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unknown network '{}'. Expected a prefix or one of: {}",
+                    network,
+                    SS58_NETWORKS
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })?,
+    };
+    validate_prefix(prefix)?;
+    Ok(prefix)
+}
+
+/// Convert address to ss58 with the generic substrate prefix
 /// 0xD43593C715FDD31C61141ABD04A99FD6822C8558854CCDE39A5684E7A56DA27D => 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY
 pub fn address_to_ss58(account: &AccountAddress) -> String {
-    let mut ss58_address = [0; 35];
-    ss58_address[0] = 42;
-    ss58_address[1..33].copy_from_slice(&account.into_bytes());
-    let hash = ss58hash(&ss58_address[0..33]);
-    ss58_address[33..35].copy_from_slice(&hash.as_bytes()[0..2]);
+    encode(account, DEFAULT_SS58_PREFIX)
+}
+
+/// Convert address to ss58 with the given network prefix
+/// 0xD43593C715FDD31C61141ABD04A99FD6822C8558854CCDE39A5684E7A56DA27D, 105 => gkQ5K6EnLRgZkwozG8GiBAEnJyM6FxzbSaSmVhKJ2w8FcK7ih
+pub fn address_to_ss58_with_prefix(account: &AccountAddress, prefix: u16) -> Result<String> {
+    validate_prefix(prefix)?;
+    Ok(encode(account, prefix))
+}
+
+fn encode(account: &AccountAddress, prefix: u16) -> String {
+    let mut ss58_address = encode_prefix(prefix);
+    ss58_address.extend_from_slice(&account.into_bytes());
+    let hash = ss58hash(&ss58_address);
+    ss58_address.extend_from_slice(&hash.as_bytes()[0..CHECK_SUM_LEN]);
     ss58_address.to_base58()
 }
 
+/// Prefixes below 64 take one byte, the rest take two bytes.
+fn encode_prefix(prefix: u16) -> Vec<u8> {
+    if prefix < 64 {
+        vec![prefix as u8]
+    } else {
+        vec![
+            ((prefix & 0b1111_1100) >> 2) as u8 | 0b0100_0000,
+            ((prefix >> 8) | ((prefix & 0b0000_0011) << 6)) as u8,
+        ]
+    }
+}
+
+/// Returns the prefix and its length in bytes.
+fn decode_prefix(data: &[u8]) -> Result<(u16, usize)> {
+    match data {
+        [first @ 0..=63, ..] => Ok((*first as u16, 1)),
+        [first @ 64..=127, second, ..] => {
+            let lower = (first << 2) | (second >> 6);
+            let upper = second & 0b0011_1111;
+            Ok((lower as u16 | (upper as u16) << 8, 2))
+        }
+        _ => Err(anyhow!("Wrong SS58 prefix")),
+    }
+}
+
 fn ss58hash(data: &[u8]) -> blake2_rfc::blake2b::Blake2bResult {
     let mut context = blake2_rfc::blake2b::Blake2b::new(64);
     context.update(SS58_PREFIX);
@@ -24,35 +115,46 @@ fn ss58hash(data: &[u8]) -> blake2_rfc::blake2b::Blake2bResult {
     context.finalize()
 }
 
-/// Convert ss58 to address
+/// Convert ss58 to address. The network prefix is ignored.
 /// 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY => 0xD43593C715FDD31C61141ABD04A99FD6822C8558854CCDE39A5684E7A56DA27D
 pub fn ss58_to_address(ss58: &str) -> Result<AccountAddress> {
+    decode_ss58(ss58).map(|(address, _)| address)
+}
+
+/// Convert ss58 to address and network prefix
+/// 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY => (0xD43593C715FDD31C61141ABD04A99FD6822C8558854CCDE39A5684E7A56DA27D, 42)
+pub fn decode_ss58(ss58: &str) -> Result<(AccountAddress, u16)> {
     let bs58 = match ss58.from_base58() {
         Ok(bs58) => bs58,
         Err(err) => return Err(anyhow!("Wrong base58:{}", err)),
     };
+    let (prefix, prefix_len) = decode_prefix(&bs58)?;
+    validate_prefix(prefix)?;
     ensure!(
-        bs58.len() > PUB_KEY_LENGTH + CHECK_SUM_LEN,
+        bs58.len() == prefix_len + PUB_KEY_LENGTH + CHECK_SUM_LEN,
         format!(
-            "Address length must be equal or greater than {} bytes",
-            PUB_KEY_LENGTH + CHECK_SUM_LEN
+            "Address length must be equal to {} bytes",
+            prefix_len + PUB_KEY_LENGTH + CHECK_SUM_LEN
         )
     );
     let check_sum = &bs58[bs58.len() - CHECK_SUM_LEN..];
-    let address = &bs58[bs58.len() - PUB_KEY_LENGTH - CHECK_SUM_LEN..bs58.len() - CHECK_SUM_LEN];
+    let address = &bs58[prefix_len..bs58.len() - CHECK_SUM_LEN];
 
     if check_sum != &ss58hash(&bs58[0..bs58.len() - CHECK_SUM_LEN]).as_bytes()[0..CHECK_SUM_LEN] {
         return Err(anyhow!("Wrong address checksum"));
     }
     let mut addr = [0; PUB_KEY_LENGTH];
     addr.copy_from_slice(address);
-    Ok(AccountAddress::new(addr))
+    Ok((AccountAddress::new(addr), prefix))
 }
 
 #[cfg(test)]
 mod tests {
     use move_core_types::account_address::AccountAddress;
-    use crate::ss58::{address_to_ss58, ss58_to_address};
+    use crate::ss58::{
+        address_to_ss58, address_to_ss58_with_prefix, decode_ss58, network_prefix,
+        ss58_to_address, validate_prefix,
+    };
 
     #[test]
     fn test_address_to_ss58() {
@@ -79,4 +181,47 @@ mod tests {
             ss58_to_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY").unwrap()
         );
     }
+
+    fn alice() -> AccountAddress {
+        AccountAddress::from_hex_literal(
+            "0xD43593C715FDD31C61141ABD04A99FD6822C8558854CCDE39A5684E7A56DA27D",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_prefixes() {
+        for (prefix, ss58) in [
+            (0, "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"),
+            (2, "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"),
+            (42, "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+            (105, "gkQ5K6EnLRgZkwozG8GiBAEnJyM6FxzbSaSmVhKJ2w8FcK7ih"),
+        ] {
+            assert_eq!(address_to_ss58_with_prefix(&alice(), prefix).unwrap(), ss58);
+            assert_eq!(decode_ss58(ss58).unwrap(), (alice(), prefix));
+        }
+
+        for prefix in [64, 255, 256, 1000, 16383] {
+            let ss58 = address_to_ss58_with_prefix(&alice(), prefix).unwrap();
+            assert_eq!(decode_ss58(&ss58).unwrap(), (alice(), prefix));
+        }
+    }
+
+    #[test]
+    fn test_validate_prefix() {
+        assert!(validate_prefix(0).is_ok());
+        assert!(validate_prefix(16383).is_ok());
+        assert!(validate_prefix(16384).is_err());
+        assert!(validate_prefix(46).is_err());
+        assert!(address_to_ss58_with_prefix(&alice(), 47).is_err());
+    }
+
+    #[test]
+    fn test_network_prefix() {
+        assert_eq!(network_prefix("pontem").unwrap(), 105);
+        assert_eq!(network_prefix("Polkadot").unwrap(), 0);
+        assert_eq!(network_prefix("1000").unwrap(), 1000);
+        assert!(network_prefix("unknown").is_err());
+        assert!(network_prefix("20000").is_err());
+    }
 }
@@ -0,0 +1,280 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{ModuleId, StructTag};
+
+use crate::{Block, BytesForBlock, Net};
+
+/// Lifetime of the cached modules of the latest state.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(600);
+
+/// Name of the cached modules of the latest state.
+const LATEST: &str = "latest";
+/// Extension of the cached modules.
+const MODULE_EXTENSION: &str = "mv";
+
+/// On-disk module cache.
+///     {dir}/{node}/{address}/{module}/{block}.mv
+/// node - genesis hash of the node or its url if the genesis is unknown.
+/// Modules of the latest state expire after the ttl. Modules of a block never expire.
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    pub dir: PathBuf,
+    pub ttl: Duration,
+}
+
+/// Net with the module cache.
+/// Modules are memoized for the lifetime of the value and stored on disk if the cache is configured.
+/// Resources are not cached.
+pub struct CachedNet {
+    net: Box<dyn Net>,
+    url: String,
+    cache: Option<CacheConfig>,
+    node_dir: RefCell<Option<PathBuf>>,
+    modules: RefCell<HashMap<(ModuleId, Option<Block>), Option<BytesForBlock>>>,
+}
+
+impl CachedNet {
+    pub fn new(net: Box<dyn Net>, url: &str, cache: Option<CacheConfig>) -> CachedNet {
+        CachedNet {
+            net,
+            url: url.to_string(),
+            cache,
+            node_dir: RefCell::new(None),
+            modules: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Cache directory of the node. The genesis hash is requested once.
+    fn node_dir(&self, cache: &CacheConfig) -> PathBuf {
+        if let Some(dir) = self.node_dir.borrow().as_ref() {
+            return dir.clone();
+        }
+        let node = match self.net.genesis_hash() {
+            Ok(Some(hash)) => hash,
+            _ => self.url.clone(),
+        };
+        let dir = cache.dir.join(file_name(&node));
+        *self.node_dir.borrow_mut() = Some(dir.clone());
+        dir
+    }
+
+    fn module_path(
+        &self,
+        cache: &CacheConfig,
+        module_id: &ModuleId,
+        height: &Option<Block>,
+    ) -> PathBuf {
+        self.node_dir(cache)
+            .join(module_id.address().to_hex_literal())
+            .join(module_id.name().as_str())
+            .join(file_name(height.as_deref().unwrap_or(LATEST)))
+            .with_extension(MODULE_EXTENSION)
+    }
+
+    /// Cached module. Expired modules of the latest state are ignored.
+    fn load(&self, path: &Path, height: &Option<Block>, ttl: Duration) -> Option<Vec<u8>> {
+        if height.is_none() {
+            let age = fs::metadata(path).ok()?.modified().ok()?.elapsed().ok()?;
+            if age > ttl {
+                return None;
+            }
+        }
+        fs::read(path).ok()
+    }
+
+    /// Writes the module to the cache. Errors are reported and ignored: the cache is optional.
+    fn store(&self, path: &Path, bytecode: &[u8]) {
+        let tmp = path.with_extension("tmp");
+        let result = path
+            .parent()
+            .map(fs::create_dir_all)
+            .transpose()
+            .and_then(|_| fs::write(&tmp, bytecode))
+            .and_then(|_| fs::rename(&tmp, path));
+        if let Err(err) = result {
+            log::warn!("Failed to cache the module {:?}: {}", path, err);
+        }
+    }
+}
+
+impl Net for CachedNet {
+    fn get_module(
+        &self,
+        module_id: &ModuleId,
+        height: &Option<Block>,
+    ) -> Result<Option<BytesForBlock>> {
+        let key = (module_id.clone(), height.clone());
+        if let Some(module) = self.modules.borrow().get(&key) {
+            return Ok(module.clone());
+        }
+
+        let path = self
+            .cache
+            .as_ref()
+            .map(|cache| (self.module_path(cache, module_id, height), cache.ttl));
+        let cached = path
+            .as_ref()
+            .and_then(|(path, ttl)| self.load(path, height, *ttl))
+            .map(|bytecode| BytesForBlock(bytecode, height.clone().unwrap_or_default()));
+        let module = match cached {
+            Some(module) => Some(module),
+            None => {
+                let module = self.net.get_module(module_id, height)?;
+                if let (Some((path, _)), Some(module)) = (&path, &module) {
+                    self.store(path, &module.0);
+                }
+                module
+            }
+        };
+
+        self.modules.borrow_mut().insert(key, module.clone());
+        Ok(module)
+    }
+
+    fn get_resource(
+        &self,
+        address: &AccountAddress,
+        tag: &StructTag,
+        height: &Option<Block>,
+    ) -> Result<Option<BytesForBlock>> {
+        self.net.get_resource(address, tag, height)
+    }
+
+    fn genesis_hash(&self) -> Result<Option<Block>> {
+        self.net.genesis_hash()
+    }
+}
+
+/// File name of the node or the block: characters other than [A-Za-z0-9-] are replaced with _
+fn file_name(name: &str) -> String {
+    name.chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::fs;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    use anyhow::Result;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::{ModuleId, StructTag};
+
+    use super::{file_name, CacheConfig, CachedNet};
+    use crate::{Block, BytesForBlock, Net};
+
+    /// Counts the module requests.
+    struct CountingNet(Rc<Cell<usize>>);
+
+    impl Net for CountingNet {
+        fn get_module(
+            &self,
+            _module_id: &ModuleId,
+            height: &Option<Block>,
+        ) -> Result<Option<BytesForBlock>> {
+            self.0.set(self.0.get() + 1);
+            Ok(Some(BytesForBlock(
+                vec![1, 2, 3],
+                height.clone().unwrap_or_default(),
+            )))
+        }
+
+        fn get_resource(
+            &self,
+            _address: &AccountAddress,
+            _tag: &StructTag,
+            _height: &Option<Block>,
+        ) -> Result<Option<BytesForBlock>> {
+            Ok(None)
+        }
+    }
+
+    fn module_id() -> ModuleId {
+        ModuleId::new(
+            AccountAddress::from_hex_literal("0x1").unwrap(),
+            Identifier::new("Hash").unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(
+            file_name("http://127.0.0.1:9933/"),
+            "http___127_0_0_1_9933_"
+        );
+        assert_eq!(file_name("0x1f"), "0x1f");
+    }
+
+    #[test]
+    fn test_memoization() {
+        let requests = Rc::new(Cell::new(0));
+        let net = CachedNet::new(
+            Box::new(CountingNet(requests.clone())),
+            "http://127.0.0.1:9933",
+            None,
+        );
+        net.get_module(&module_id(), &None).unwrap();
+        net.get_module(&module_id(), &None).unwrap();
+        assert_eq!(requests.get(), 1);
+        net.get_module(&module_id(), &Some("100".to_string()))
+            .unwrap();
+        assert_eq!(requests.get(), 2);
+    }
+
+    #[test]
+    fn test_disk_cache() {
+        let dir = std::env::temp_dir().join(format!("net_cache_test_{}", std::process::id()));
+        let requests = Rc::new(Cell::new(0));
+        let cache = CacheConfig {
+            dir: dir.clone(),
+            ttl: Duration::from_secs(600),
+        };
+        let new_net = |cache: CacheConfig| {
+            CachedNet::new(
+                Box::new(CountingNet(requests.clone())),
+                "http://127.0.0.1:9933",
+                Some(cache),
+            )
+        };
+
+        let module = new_net(cache.clone())
+            .get_module(&module_id(), &None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(module.0, vec![1, 2, 3]);
+        assert_eq!(requests.get(), 1);
+
+        // The next run reads the module from the disk.
+        new_net(cache.clone())
+            .get_module(&module_id(), &None)
+            .unwrap();
+        assert_eq!(requests.get(), 1);
+
+        // Expired.
+        let expired = CacheConfig {
+            dir: dir.clone(),
+            ttl: Duration::from_secs(0),
+        };
+        std::thread::sleep(Duration::from_millis(10));
+        new_net(expired).get_module(&module_id(), &None).unwrap();
+        assert_eq!(requests.get(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
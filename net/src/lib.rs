@@ -8,8 +8,10 @@ use move_core_types::resolver::{ModuleResolver, ResourceResolver};
 #[cfg(feature = "dfinance")]
 mod dnode;
 
+mod cache;
 mod pont;
 use crate::pont::PontNet;
+pub use crate::cache::{CacheConfig, CachedNet, DEFAULT_CACHE_TTL};
 
 pub type Block = String;
 
@@ -21,10 +23,24 @@ where
 
     Ok(Box::new(PontNet {
         api: uri.to_string(),
+        block_hashes: Default::default(),
     }))
 }
 
-#[derive(Debug)]
+/// Net with the module cache. Without the cache config the modules are memoized in memory only.
+pub fn make_cached_net<T>(uri: T, cache: Option<CacheConfig>) -> Result<Box<dyn Net>>
+where
+    T: Into<Url>,
+{
+    let uri = uri.into();
+    Ok(Box::new(CachedNet::new(
+        make_net(uri.clone())?,
+        uri.as_str(),
+        cache,
+    )))
+}
+
+#[derive(Debug, Clone)]
 pub struct BytesForBlock(pub Vec<u8>, pub Block);
 
 pub trait Net {
@@ -40,6 +56,11 @@ pub trait Net {
         tag: &StructTag,
         height: &Option<Block>,
     ) -> Result<Option<BytesForBlock>>;
+
+    /// Hash of the genesis block. Identifies the chain of the node.
+    fn genesis_hash(&self) -> Result<Option<Block>> {
+        Ok(None)
+    }
 }
 
 pub struct NetView {
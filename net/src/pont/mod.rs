@@ -1,5 +1,7 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use serde::{Serialize, Deserialize};
 
 use move_core_types::language_storage::{ModuleId, StructTag};
@@ -12,6 +14,64 @@ pub type Block = String;
 
 pub struct PontNet {
     pub(crate) api: String,
+    /// Hashes of the blocks resolved by number.
+    pub(crate) block_hashes: RefCell<HashMap<u64, String>>,
+}
+
+impl PontNet {
+    /// Hash of the block to query the state at.
+    /// The height is a block number or a block hash (0x...). None is the best block.
+    fn block_hash(&self, height: &Option<Block>) -> Result<Option<String>> {
+        let height = match height {
+            Some(height) => height.trim(),
+            None => return Ok(None),
+        };
+        if height.starts_with("0x") {
+            return Ok(Some(height.to_string()));
+        }
+        let number: u64 = height
+            .parse()
+            .map_err(|_| anyhow!("Invalid block number or hash: {}", height))?;
+        if let Some(hash) = self.block_hashes.borrow().get(&number) {
+            return Ok(Some(hash.to_owned()));
+        }
+
+        let req = Request {
+            id: 1,
+            jsonrpc: "2.0",
+            method: "chain_getBlockHash",
+            params: vec![format!("0x{:x}", number)],
+        };
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            "Content-Type",
+            reqwest::header::HeaderValue::from_static("application/json"),
+        );
+        let response = reqwest::blocking::Client::new()
+            .post(&self.api)
+            .headers(headers)
+            .json(&req)
+            .send()?;
+        if response.status() != 200 {
+            bail!(
+                "Failed to get block hash :{}. Error:{}",
+                number,
+                response.status()
+            );
+        }
+
+        let resp = response.json::<Response>()?;
+        if let Some(err) = resp.error {
+            bail!("{:?}", err);
+        }
+        match resp.result {
+            Some(hash) => {
+                self.block_hashes.borrow_mut().insert(number, hash.clone());
+                Ok(Some(hash))
+            }
+            None => bail!("Block {} not found", number),
+        }
+    }
 }
 
 impl Net for PontNet {
@@ -20,11 +80,13 @@ impl Net for PontNet {
         module_id: &ModuleId,
         height: &Option<Block>,
     ) -> Result<Option<BytesForBlock>> {
+        let mut params = vec![format!("0x{}", hex::encode(bcs::to_bytes(module_id)?))];
+        params.extend(self.block_hash(height)?);
         let req = Request {
             id: 1,
             jsonrpc: "2.0",
             method: "mvm_getModule",
-            params: vec![format!("0x{}", hex::encode(bcs::to_bytes(module_id)?))],
+            params,
         };
 
         let mut headers = reqwest::header::HeaderMap::new();
@@ -66,14 +128,16 @@ impl Net for PontNet {
         tag: &StructTag,
         height: &Option<Block>,
     ) -> Result<Option<BytesForBlock>> {
+        let mut params = vec![
+            address_to_ss58(address),
+            format!("0x{}", hex::encode(bcs::to_bytes(&tag)?)),
+        ];
+        params.extend(self.block_hash(height)?);
         let req = Request {
             id: 1,
             jsonrpc: "2.0",
             method: "mvm_getResource",
-            params: vec![
-                address_to_ss58(address),
-                format!("0x{}", hex::encode(bcs::to_bytes(&tag)?)),
-            ],
+            params,
         };
         let mut headers = reqwest::header::HeaderMap::new();
         headers.insert(
@@ -108,6 +172,10 @@ impl Net for PontNet {
             Ok(None)
         }
     }
+
+    fn genesis_hash(&self) -> Result<Option<Block>> {
+        self.block_hash(&Some("0".to_string()))
+    }
 }
 
 #[derive(Serialize)]
@@ -161,6 +229,7 @@ mod tests {
     fn test_get_module() {
         let api = PontNet {
             api: "http://localhost:9933".to_string(),
+            block_hashes: Default::default(),
         };
         let module = api
             .get_module(
@@ -191,6 +260,7 @@ mod tests {
     fn test_get_resource() {
         let api = PontNet {
             api: "http://localhost:9933".to_string(),
+            block_hashes: Default::default(),
         };
 
         let adr = ss58_to_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY").unwrap();
@@ -108,6 +108,99 @@ impl PontemClient {
         }
     }
 
+    /// Sequential transaction execution.
+    /// Transactions are executed one after another and are not atomic:
+    /// the sequence stops at the first failed transaction, the executed ones stay committed.
+    ///     transaction_paths: The paths to the transaction files. PATH/TO/TRANSACTION/FILE.mvt
+    ///     gas: Gas limit for each transaction.
+    ///     key_phrase: secret keyphrase
+    pub fn tx_mvm_execute_sequence(
+        &self,
+        transaction_paths: &[&str],
+        gas: u64,
+        key_phrase: &str,
+    ) -> Result<Vec<String>> {
+        unsafe {
+            let func: libloading::Symbol<FnInterface> =
+                self.lib.get(b"tx_mvm_execute_sequence")?;
+            func(
+                &transaction_paths.join("\n"),
+                self.url.as_str(),
+                gas,
+                key_phrase,
+            )
+            .map(|hashes| hashes.lines().map(String::from).collect())
+        }
+    }
+
+    /// (DEV) Sequential transaction execution.
+    ///     transaction_paths: The paths to the transaction files. PATH/TO/TRANSACTION/FILE.mvt
+    ///     gas: Gas limit for each transaction.
+    ///     test_account: alias or ss58 address of the test account. //Alice, alice, bob... or 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY
+    pub fn tx_mvm_execute_sequence_dev(
+        &self,
+        transaction_paths: &[&str],
+        gas: u64,
+        test_account: &str,
+    ) -> Result<Vec<String>> {
+        unsafe {
+            let func: libloading::Symbol<FnInterface> =
+                self.lib.get(b"tx_mvm_execute_sequence_dev")?;
+            func(
+                &transaction_paths.join("\n"),
+                self.url.as_str(),
+                gas,
+                test_account,
+            )
+            .map(|hashes| hashes.lines().map(String::from).collect())
+        }
+    }
+
+    /// Atomic transaction execution.
+    /// Transactions are executed in one `Utility.batch_all` extrinsic: all of them or none.
+    /// Returns the hash of the batch extrinsic.
+    ///     transaction_paths: The paths to the transaction files. PATH/TO/TRANSACTION/FILE.mvt
+    ///     gas: Gas limit for each transaction.
+    ///     key_phrase: secret keyphrase
+    pub fn tx_mvm_execute_batch(
+        &self,
+        transaction_paths: &[&str],
+        gas: u64,
+        key_phrase: &str,
+    ) -> Result<String> {
+        unsafe {
+            let func: libloading::Symbol<FnInterface> = self.lib.get(b"tx_mvm_execute_batch")?;
+            func(
+                &transaction_paths.join("\n"),
+                self.url.as_str(),
+                gas,
+                key_phrase,
+            )
+        }
+    }
+
+    /// (DEV) Atomic transaction execution.
+    ///     transaction_paths: The paths to the transaction files. PATH/TO/TRANSACTION/FILE.mvt
+    ///     gas: Gas limit for each transaction.
+    ///     test_account: alias or ss58 address of the test account. //Alice, alice, bob... or 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY
+    pub fn tx_mvm_execute_batch_dev(
+        &self,
+        transaction_paths: &[&str],
+        gas: u64,
+        test_account: &str,
+    ) -> Result<String> {
+        unsafe {
+            let func: libloading::Symbol<FnInterface> =
+                self.lib.get(b"tx_mvm_execute_batch_dev")?;
+            func(
+                &transaction_paths.join("\n"),
+                self.url.as_str(),
+                gas,
+                test_account,
+            )
+        }
+    }
+
     /// Publishing the package
     ///     package_path: The path to the package file. PATH/TO/PACKAGE/FILE.mv
     ///     gas: Gas limit for transaction execution.
@@ -142,6 +235,16 @@ impl PontemClient {
         }
     }
 
+    /// SS58 address of the account.
+    ///     key_phrase: secret keyphrase
+    pub fn ss58_address(&self, key_phrase: &str) -> Result<String> {
+        unsafe {
+            let func: libloading::Symbol<unsafe fn(&str) -> Result<String>> =
+                self.lib.get(b"ss58_address")?;
+            func(key_phrase)
+        }
+    }
+
     /// Library Version
     pub fn version(&self) -> Result<String> {
         let result = unsafe {
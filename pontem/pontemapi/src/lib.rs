@@ -144,6 +144,75 @@ pub fn tx_mvm_execute_dev(
     result
 }
 
+/// Public interface for sequential transaction execution
+/// The transactions are submitted one after another. All files are checked before the first submission.
+/// The sequence is not atomic (see tx_mvm_execute_batch): it stops at the first failed transaction,
+/// and the transactions executed before it stay committed.
+///     transaction_paths: The paths to the transaction files separated by a new line.
+///     url: Node address. ws://127.0.0.1:9944
+///     gas: Gas limit for each transaction.
+///     key_phrase: secret keyphrase
+#[export_name = "tx_mvm_execute_sequence"]
+pub fn tx_mvm_execute_sequence(
+    transaction_paths: &str,
+    url_str: &str,
+    gas: u64,
+    key_phrase: &str,
+) -> Result<String> {
+    let pair = sr25519Pair::from_string(key_phrase, None).map_err(|err| anyhow!("{:?}", err))?;
+    execute_sequence(transaction_paths, url_str, gas, pair)
+}
+
+/// (DEV) Public interface for sequential transaction execution
+///     transaction_paths: The paths to the transaction files separated by a new line.
+///     url: Node address. ws://127.0.0.1:9944
+///     gas: Gas limit for each transaction.
+///     test_signer: alias or ss58 address of the test account. //Alice, alice, bob... or 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY
+#[export_name = "tx_mvm_execute_sequence_dev"]
+pub fn tx_mvm_execute_sequence_dev(
+    transaction_paths: &str,
+    url_str: &str,
+    gas: u64,
+    test_signer: &str,
+) -> Result<String> {
+    let pair = test_keyring_from_str(test_signer)?.pair();
+    execute_sequence(transaction_paths, url_str, gas, pair)
+}
+
+/// Public interface for atomic transaction execution
+/// The transactions are submitted as one `Utility.batch_all` extrinsic:
+/// either all of them are executed or none. Requires the Utility pallet in the node runtime.
+///     transaction_paths: The paths to the transaction files separated by a new line.
+///     url: Node address. ws://127.0.0.1:9944
+///     gas: Gas limit for each transaction.
+///     key_phrase: secret keyphrase
+#[export_name = "tx_mvm_execute_batch"]
+pub fn tx_mvm_execute_batch(
+    transaction_paths: &str,
+    url_str: &str,
+    gas: u64,
+    key_phrase: &str,
+) -> Result<String> {
+    let pair = sr25519Pair::from_string(key_phrase, None).map_err(|err| anyhow!("{:?}", err))?;
+    execute_batch(transaction_paths, url_str, gas, pair)
+}
+
+/// (DEV) Public interface for atomic transaction execution
+///     transaction_paths: The paths to the transaction files separated by a new line.
+///     url: Node address. ws://127.0.0.1:9944
+///     gas: Gas limit for each transaction.
+///     test_signer: alias or ss58 address of the test account. //Alice, alice, bob... or 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY
+#[export_name = "tx_mvm_execute_batch_dev"]
+pub fn tx_mvm_execute_batch_dev(
+    transaction_paths: &str,
+    url_str: &str,
+    gas: u64,
+    test_signer: &str,
+) -> Result<String> {
+    let pair = test_keyring_from_str(test_signer)?.pair();
+    execute_batch(transaction_paths, url_str, gas, pair)
+}
+
 /// Public interface for publishing the package
 ///     package_path: The path to the package file. PATH/TO/PACKAGE/FILE.mv
 ///     url: Node address. ws://127.0.0.1:9944
@@ -192,6 +261,14 @@ pub fn tx_mvm_publish_package_dev(
     result
 }
 
+/// SS58 address of the account
+///     key_phrase: secret keyphrase
+#[export_name = "ss58_address"]
+pub fn ss58_address(key_phrase: &str) -> Result<String> {
+    let pair = sr25519Pair::from_string(key_phrase, None).map_err(|err| anyhow!("{:?}", err))?;
+    Ok(AccountId32::new(pair.public().0).to_ss58check())
+}
+
 /// Library Version
 #[no_mangle]
 pub fn version() -> String {
@@ -314,6 +391,165 @@ async fn execute(context: Context) -> Result<String> {
     }
 }
 
+/// Sequential transaction execution
+/// Returns the transaction hashes separated by a new line.
+fn execute_sequence(
+    transaction_paths: &str,
+    url_str: &str,
+    gas: u64,
+    pair: sr25519Pair,
+) -> Result<String> {
+    let contexts = transaction_paths
+        .lines()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(|path| Context::from_pair(path, url_str, gas, pair.clone()))
+        .collect::<Result<Vec<_>>>()?;
+    ensure!(!contexts.is_empty(), "The sequence is empty");
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let total = contexts.len();
+    let mut hashes = Vec::with_capacity(total);
+    for (index, context) in contexts.into_iter().enumerate() {
+        debug!(
+            "fn tx_mvm_execute_sequence [{}]:\n{}",
+            index,
+            context.debug()
+        );
+        let path = context.path_file.display().to_string();
+        match runtime.block_on(execute(context)) {
+            Ok(hash) => hashes.push(hash),
+            Err(err) => {
+                return Err(anyhow!(
+                    "Transaction {index} of {total} failed: {path}\n\
+                    {err}\n\
+                    Executed transactions: {executed}. They stay committed on chain:\n\
+                    {hashes}\n\
+                    The rest of the sequence was not submitted.",
+                    index = index + 1,
+                    total = total,
+                    path = path,
+                    err = err,
+                    executed = hashes.len(),
+                    hashes = hashes.join("\n"),
+                ))
+            }
+        }
+    }
+    Ok(hashes.join("\n"))
+}
+
+/// `Utility.batch_all` call: dispatches the calls atomically.
+/// The call is not in the library metadata, so it is encoded by the metadata of the node.
+#[derive(codec::Encode)]
+struct BatchAll {
+    calls: Vec<subxt::Encoded>,
+}
+
+impl subxt::Call for BatchAll {
+    const PALLET: &'static str = "Utility";
+    const FUNCTION: &'static str = "batch_all";
+}
+
+/// Atomic transaction execution
+/// Returns the hash of the batch extrinsic.
+fn execute_batch(
+    transaction_paths: &str,
+    url_str: &str,
+    gas: u64,
+    pair: sr25519Pair,
+) -> Result<String> {
+    let contexts = transaction_paths
+        .lines()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(|path| Context::from_pair(path, url_str, gas, pair.clone()))
+        .collect::<Result<Vec<_>>>()?;
+    ensure!(!contexts.is_empty(), "The batch is empty");
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(batch(contexts))
+}
+
+/// Execution of the transactions in one `Utility.batch_all` extrinsic
+async fn batch(contexts: Vec<Context>) -> Result<String> {
+    let context = &contexts[0];
+    debug!("fn tx_mvm_execute_batch:\n{}", context.debug());
+    let signer_pair: PairSigner<DefaultConfig, sr25519Pair> =
+        PairSigner::new(context.pair.clone());
+
+    let api = ClientBuilder::new()
+        .set_url(context.url.clone())
+        .build()
+        .await?
+        .to_runtime_api::<pontem::RuntimeApi<pontem::DefaultConfig>>();
+
+    // It is necessary to decrypt the message
+    let metadata = api.client.metadata();
+    metadata.pallet("Utility").map_err(|_| {
+        anyhow!("The node runtime has no Utility pallet: the transactions cannot be executed atomically")
+    })?;
+    let mvm = metadata.pallet("Mvm")?;
+
+    let mut calls = Vec::with_capacity(contexts.len());
+    for context in &contexts {
+        debug!("Reading a file: {}", context.path_file.display());
+        calls.push(mvm.encode_call(pontem::mvm::calls::Execute {
+            tx_bc: fs::read(&context.path_file)?,
+            gas_limit: context.gas,
+        })?);
+    }
+
+    let hash = subxt::SubmittableExtrinsic::new(&api.client, BatchAll { calls })
+        .sign_and_submit(&signer_pair)
+        .await?
+        .to_string();
+
+    // Only for Websocket you can get the result of publishing
+    if !context.is_connection_ws() {
+        return Ok(hash);
+    }
+
+    // Subscribe to events
+    let sub = api.client.rpc().subscribe_events().await?;
+    let decoder = api.client.events_decoder();
+    let mut sub = EventSubscription::<pontem::DefaultConfig>::new(sub, decoder);
+
+    let mut completed = false;
+    loop {
+        let raw = sub.next().await.ok_or(anyhow!("No response received"))??;
+
+        debug!("event {}", raw.variant.as_str());
+        match raw.variant.as_str() {
+            // All the transactions of the batch are executed
+            "BatchCompleted" => completed = true,
+            "ExtrinsicSuccess" => {
+                if completed {
+                    return Ok(hash);
+                }
+            }
+            // Called when a transaction of the batch fails. The whole batch is reverted
+            "ExtrinsicFailed" => {
+                let answer = <pontem::system::events::ExtrinsicFailed as codec::Decode>::decode(
+                    &mut &raw.data[..],
+                )?;
+                return Err(anyhow!(
+                    "The batch is reverted, no transaction was executed.\n{}",
+                    dispatcherror_to_string(answer.0, metadata)
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
 /// Publish a package
 async fn pb_package_dev(context: Context) -> Result<String> {
     debug!("Reading a file: {}", context.path_file.display());
@@ -539,7 +775,8 @@ mod tests {
     use log::debug;
     use crate::{
         test_keyring_from_str, tx_mvm_publish_module_dev, tx_mvm_execute_dev,
-        tx_mvm_publish_package_dev, version, tx_mvm_publish_module,
+        tx_mvm_publish_package_dev, version, tx_mvm_publish_module, tx_mvm_execute_sequence_dev,
+        tx_mvm_execute_batch_dev, ss58_address,
     };
 
     #[test]
@@ -555,6 +792,30 @@ mod tests {
         tx_mvm_execute_dev("./Alice_Main.mvt", "ws://127.0.0.1:9944", 100, "//Alice").unwrap();
     }
 
+    #[test]
+    #[ignore]
+    fn test_tx_mvm_execute_sequence_dev_ws() {
+        tx_mvm_execute_sequence_dev(
+            "./Alice_Main.mvt\n./Alice_Main.mvt",
+            "ws://127.0.0.1:9944",
+            100,
+            "//Alice",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[ignore]
+    fn test_tx_mvm_execute_batch_dev_ws() {
+        tx_mvm_execute_batch_dev(
+            "./Alice_Main.mvt\n./Alice_Main.mvt",
+            "ws://127.0.0.1:9944",
+            100,
+            "//Alice",
+        )
+        .unwrap();
+    }
+
     #[test]
     #[ignore]
     fn test_tx_mvm_publish_package_dev_ws() {
@@ -582,6 +843,15 @@ mod tests {
         debug!("{}", version());
     }
 
+    #[test]
+    fn test_ss58_address() {
+        assert_eq!(
+            ss58_address("//Alice").unwrap(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        assert!(ss58_address("").is_err());
+    }
+
     #[test]
     #[ignore]
     fn test_key_pair() {
@@ -1,6 +1,6 @@
 #![allow(clippy::field_reassign_with_default)]
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use move_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue};
 
 #[cfg(feature = "json-schema")]
@@ -15,6 +15,12 @@ pub fn produce_json_schema() -> RootSchema {
     schema_for!(AnnotatedMoveStructExt)
 }
 
+/// JSON schema of the JS-safe output: [JsMoveStruct].
+#[cfg(feature = "json-schema")]
+pub fn produce_js_json_schema() -> RootSchema {
+    schema_for!(JsMoveStruct)
+}
+
 #[derive(Serialize)]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub struct AnnotatedMoveStructWrapper {
@@ -24,6 +30,99 @@ pub struct AnnotatedMoveStructWrapper {
     #[serde(with = "AnnotatedMoveStructExt")]
     pub result: AnnotatedMoveStruct,
 }
+
+/// JS-safe variant of [AnnotatedMoveStructWrapper].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct JsMoveStructWrapper {
+    /// Block number, current for the state
+    pub height: String,
+
+    pub result: JsMoveStruct,
+}
+
+impl JsMoveStructWrapper {
+    pub fn new(height: String, result: &AnnotatedMoveStruct) -> JsMoveStructWrapper {
+        JsMoveStructWrapper {
+            height,
+            result: JsMoveStruct::from(result),
+        }
+    }
+}
+
+/// Annotated resource which can be consumed by JavaScript without the loss of precision:
+///     addresses are hex strings: "0x1"
+///     u64 and u128 are decimal strings: "18446744073709551615"
+///     type tags are canonical strings: "0x1::Account::Balance<0x1::PONT::PONT>"
+///     bytes are hex strings: "0x0102"
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct JsMoveStruct {
+    pub abilities: u8,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub value: Vec<JsMoveField>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct JsMoveField {
+    pub id: String,
+    pub value: JsMoveValue,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub enum JsMoveValue {
+    U8(u8),
+    U64(String),
+    U128(String),
+    Bool(bool),
+    Address(String),
+    Vector(String, Vec<JsMoveValue>),
+    Bytes(String),
+    Struct(JsMoveStruct),
+}
+
+impl From<&AnnotatedMoveStruct> for JsMoveStruct {
+    fn from(st: &AnnotatedMoveStruct) -> Self {
+        JsMoveStruct {
+            abilities: st.abilities.into_u8(),
+            type_: st.type_.to_string(),
+            value: st
+                .value
+                .iter()
+                .map(|(id, value)| JsMoveField {
+                    id: id.to_string(),
+                    value: JsMoveValue::from(value),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&AnnotatedMoveValue> for JsMoveValue {
+    fn from(value: &AnnotatedMoveValue) -> Self {
+        match value {
+            AnnotatedMoveValue::U8(value) => JsMoveValue::U8(*value),
+            AnnotatedMoveValue::U64(value) => JsMoveValue::U64(value.to_string()),
+            AnnotatedMoveValue::U128(value) => JsMoveValue::U128(value.to_string()),
+            AnnotatedMoveValue::Bool(value) => JsMoveValue::Bool(*value),
+            AnnotatedMoveValue::Address(address) => {
+                JsMoveValue::Address(address.to_hex_literal())
+            }
+            AnnotatedMoveValue::Vector(tp, values) => JsMoveValue::Vector(
+                tp.to_string(),
+                values.iter().map(JsMoveValue::from).collect(),
+            ),
+            AnnotatedMoveValue::Bytes(bytes) => {
+                JsMoveValue::Bytes(format!("0x{}", hex::encode(bytes)))
+            }
+            AnnotatedMoveValue::Struct(st) => JsMoveValue::Struct(JsMoveStruct::from(st)),
+        }
+    }
+}
+
 #[derive(Serialize)]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 #[serde(remote = "move_resource_viewer::AnnotatedMoveStruct")]
@@ -203,3 +302,67 @@ mod schema_support {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use move_binary_format::file_format::AbilitySet;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::{StructTag, TypeTag};
+    use move_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue};
+
+    use super::{JsMoveStructWrapper, JsMoveValue};
+
+    fn id(name: &str) -> Identifier {
+        Identifier::new(name).unwrap()
+    }
+
+    #[test]
+    fn test_js_round_trip() {
+        let address = AccountAddress::from_hex_literal("0x2").unwrap();
+        let inner = AnnotatedMoveStruct {
+            abilities: AbilitySet::EMPTY,
+            type_: StructTag {
+                address,
+                module: id("Store"),
+                name: id("Inner"),
+                type_params: vec![],
+            },
+            value: vec![(id("flag"), AnnotatedMoveValue::Bool(true))],
+        };
+        let resource = AnnotatedMoveStruct {
+            abilities: AbilitySet::EMPTY,
+            type_: StructTag {
+                address,
+                module: id("Store"),
+                name: id("Outer"),
+                type_params: vec![],
+            },
+            value: vec![
+                (id("small"), AnnotatedMoveValue::U8(7)),
+                (id("big"), AnnotatedMoveValue::U64(u64::MAX)),
+                (id("huge"), AnnotatedMoveValue::U128(u128::MAX)),
+                (id("owner"), AnnotatedMoveValue::Address(address)),
+                (id("bytes"), AnnotatedMoveValue::Bytes(vec![1, 2])),
+                (
+                    id("items"),
+                    AnnotatedMoveValue::Vector(TypeTag::U64, vec![AnnotatedMoveValue::U64(1)]),
+                ),
+                (id("inner"), AnnotatedMoveValue::Struct(inner)),
+            ],
+        };
+
+        let wrapper = JsMoveStructWrapper::new("100".to_string(), &resource);
+        let json = serde_json::to_string(&wrapper).unwrap();
+        assert!(json.contains("\"18446744073709551615\""));
+        assert!(json.contains("\"340282366920938463463374607431768211455\""));
+
+        let parsed: JsMoveStructWrapper = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, wrapper);
+        assert_eq!(parsed.result.type_, "0x2::Store::Outer");
+        assert_eq!(
+            parsed.result.value[1].value,
+            JsMoveValue::U64("18446744073709551615".to_string())
+        );
+    }
+}